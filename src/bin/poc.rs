@@ -1,36 +1,225 @@
-// use poc::{PolicyCompiler, CompilationStatus};
-
-fn main() {
-    println!("{} layer running...", env!("CARGO_PKG_NAME"));
-    loop {
-        std::thread::sleep(std::time::Duration::from_secs(3600));
-    }
-/*
-    println!("Policy-to-Outcome Compiler (POC)");
-    println!("=================================\n");
-    
-    let compiler = PolicyCompiler::new();
-    
-    // Example policy compilation
-    let policy = "All actions must be logged by SYSTEM. \
-                  Cost of operations must not exceed 1000 USD per month by SERVICE.";
-    
-    println!("Input Policy:");
-    println!("  {}\n", policy);
-    
-    let result = compiler.compile(policy);
-    
+use poc::{CompilationResult, CompilationStatus, DiagnosticMode, Evaluator, PolicyCompiler, PolicyEvent, Principal, PrincipalRegistry};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: poc compile <FILE|-> [--out-dir <DIR>] [--report-all] [--markdown] [--principal <NAME=KIND>]...
+       poc evaluate <RESULT> <EVENTS|->
+
+Compiles a policy document into DIO, ZT-AAS and ICAE artifacts, or checks a
+JSON-lines event stream against a compiled result.json.
+
+Arguments:
+  <FILE|->           Policy file to compile, or '-' to read from stdin
+  <RESULT>           result.json written by 'poc compile'
+  <EVENTS|->         JSON-lines event file, or '-' to read from stdin
+
+Options:
+  --out-dir <DIR>    Directory to write artifact JSON files to (default: poc-out)
+  --report-all       Report every diagnostic instead of stopping at the first failing pass
+  --markdown         Read the policy as Markdown (default for .md and .markdown files)
+  --principal <NAME=KIND>
+                     Declare a named principal; KIND is human, user, service or system
+  -h, --help         Print this help
+
+Exit status: 0 on PASS or no violations, 1 on FAIL or violations, 2 on usage or I/O errors.";
+
+/// Default directory for compiled artifacts.
+const DEFAULT_OUT_DIR: &str = "poc-out";
+
+/// Parsed arguments for the `compile` subcommand.
+#[derive(Debug)]
+struct CompileArgs {
+    input: String,
+    out_dir: PathBuf,
+    mode: DiagnosticMode,
+    markdown: bool,
+    principals: PrincipalRegistry,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            eprintln!("\n{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Dispatches the subcommand and returns the process exit code.
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    match args.first().map(String::as_str) {
+        Some("compile") => compile(parse_compile_args(&args[1..])?),
+        Some("evaluate") => match &args[1..] {
+            [result, events] => evaluate(result, events),
+            _ => Err("evaluate expects <RESULT> and <EVENTS|->".to_string()),
+        },
+        Some("-h") | Some("--help") | Some("help") => {
+            println!("{}", USAGE);
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(other) => Err(format!("unknown command '{}'", other)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_compile_args(args: &[String]) -> Result<CompileArgs, String> {
+    let mut input = None;
+    let mut out_dir = PathBuf::from(DEFAULT_OUT_DIR);
+    let mut mode = DiagnosticMode::FailFast;
+    let mut markdown = false;
+    let mut principals = PrincipalRegistry::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out-dir" => {
+                let dir = iter.next().ok_or("--out-dir requires a directory")?;
+                out_dir = PathBuf::from(dir);
+            }
+            "--report-all" => mode = DiagnosticMode::ReportAll,
+            "--markdown" => markdown = true,
+            "--principal" => {
+                let spec = iter.next().ok_or("--principal requires NAME=KIND")?;
+                let (name, kind) = parse_principal(spec)?;
+                principals.register(name, kind);
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option '{}'", flag)),
+            path => {
+                if input.replace(path.to_string()).is_some() {
+                    return Err("expected exactly one policy input".to_string());
+                }
+            }
+        }
+    }
+
+    let input = input.ok_or("missing policy input (use '-' for stdin)")?;
+    let extension = Path::new(&input).extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+    let markdown = markdown || matches!(extension.as_deref(), Some("md") | Some("markdown"));
+
+    Ok(CompileArgs {
+        input,
+        out_dir,
+        mode,
+        markdown,
+        principals,
+    })
+}
+
+/// Parses a `NAME=KIND` principal declaration.
+fn parse_principal(spec: &str) -> Result<(&str, Principal), String> {
+    let (name, kind) = spec
+        .rsplit_once('=')
+        .ok_or_else(|| format!("invalid principal '{}': expected NAME=KIND", spec))?;
+    let kind = match kind.to_ascii_lowercase().as_str() {
+        "human" | "user" => Principal::User,
+        "service" => Principal::Service,
+        "system" => Principal::System,
+        other => return Err(format!("unknown principal kind '{}'", other)),
+    };
+    if name.trim().is_empty() {
+        return Err(format!("invalid principal '{}': name is empty", spec));
+    }
+    Ok((name, kind))
+}
+
+fn compile(args: CompileArgs) -> Result<ExitCode, String> {
+    let (source_name, policy) = read_input(&args.input)?;
+
+    let compiler = PolicyCompiler::builder()
+        .diagnostic_mode(args.mode)
+        .principal_registry(args.principals)
+        .build();
+    let result = if args.markdown { compiler.compile_markdown(&policy) } else { compiler.compile(&policy) };
+
     println!("Compilation Result: {}", result.verdict);
     println!("  Clauses: {}", result.intent_normalization.clauses.len());
     println!("  DIO Invariants: {}", result.dio_invariants.len());
     println!("  ZT Authorities: {}", result.zt_authority_graph.len());
     println!("  ICAE Constraints: {}", result.icae_constraints.len());
-    
-    if result.verdict == CompilationStatus::Fail {
-        println!("\nErrors:");
-        for err in &result.errors {
-            println!("  - {}", err);
+    println!("  Revocation Rules: {}", result.revocation_rules.len());
+
+    for err in &result.errors {
+        match err.span() {
+            Some(span) => eprintln!("{}:{}: {}", source_name, span, err),
+            None => eprintln!("{}: {}", source_name, err),
+        }
+    }
+
+    write_artifacts(&args.out_dir, &result)?;
+    println!("  Artifacts: {}", args.out_dir.display());
+
+    Ok(match result.verdict {
+        CompilationStatus::Pass => ExitCode::SUCCESS,
+        CompilationStatus::Fail => ExitCode::from(1),
+    })
+}
+
+fn evaluate(result_path: &str, events_input: &str) -> Result<ExitCode, String> {
+    let (_, result_json) = read_input(result_path)?;
+    let result: CompilationResult = serde_json::from_str(&result_json)
+        .map_err(|e| format!("invalid result '{}': {}", result_path, e))?;
+    let (source_name, events_jsonl) = read_input(events_input)?;
+
+    let mut events = Vec::new();
+    for (line_number, line) in events_jsonl.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
+        let event: PolicyEvent = serde_json::from_str(line)
+            .map_err(|e| format!("{}:{}: invalid event: {}", source_name, line_number + 1, e))?;
+        events.push(event);
+    }
+
+    let violations = Evaluator::evaluate(&result, &events);
+    println!("Events: {}", events.len());
+    println!("Violations: {}", violations.len());
+    for violation in &violations {
+        println!("  {}", violation);
+    }
+
+    Ok(if violations.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(1) })
+}
+
+/// Reads a file, or stdin when `input` is `-`, returning its display name and contents.
+fn read_input(input: &str) -> Result<(String, String), String> {
+    if input == "-" {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("failed to read stdin: {}", e))?;
+        Ok(("<stdin>".to_string(), text))
+    } else {
+        let text = fs::read_to_string(input)
+            .map_err(|e| format!("failed to read '{}': {}", input, e))?;
+        Ok((input.to_string(), text))
     }
-*/
-}
\ No newline at end of file
+}
+
+/// Writes one JSON file per artifact kind plus the full result.
+fn write_artifacts(out_dir: &Path, result: &CompilationResult) -> Result<(), String> {
+    fs::create_dir_all(out_dir)
+        .map_err(|e| format!("failed to create '{}': {}", out_dir.display(), e))?;
+
+    write_json(&out_dir.join("result.json"), result)?;
+    write_json(&out_dir.join("dio_invariants.json"), &result.dio_invariants)?;
+    write_json(&out_dir.join("zt_authority_graph.json"), &result.zt_authority_graph)?;
+    write_json(&out_dir.join("delegation_graph.json"), &result.delegation_graph)?;
+    write_json(&out_dir.join("icae_constraints.json"), &result.icae_constraints)?;
+    write_json(&out_dir.join("revocation_rules.json"), &result.revocation_rules)?;
+    write_json(&out_dir.join("traceability_map.json"), &result.traceability_map)?;
+    Ok(())
+}
+
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("failed to serialize '{}': {}", path.display(), e))?;
+    fs::write(path, json + "\n").map_err(|e| format!("failed to write '{}': {}", path.display(), e))
+}
@@ -0,0 +1,414 @@
+//! Declarative policy syntax for clauses that should not depend on free-text
+//! matching:
+//!
+//! ```text
+//! clause access-log {
+//!     principal SYSTEM;
+//!     must log "access";
+//! }
+//!
+//! budget api {
+//!     ceiling 1000 USD/month;
+//!     principal SERVICE;
+//! }
+//! ```
+//!
+//! A `clause` names its principals (`principal NAME [as ROLE];`) and one rule:
+//! `must VERB OBJECT;`, `must not VERB OBJECT;` or `can VERB OBJECT;`. A `budget`
+//! names its principals, a `ceiling AMOUNT UNIT/PERIOD;` (or `per PERIOD`) and an
+//! optional `subject`, which defaults to the budget's name. Both take conditions:
+//! `when NAME;` and `unless NAME;`. `//` starts a comment.
+//!
+//! Declarations start on a line of their own and may be mixed with natural-language
+//! clauses in the same policy.
+
+use crate::{parse_quantity, AuthorityRole, BudgetPeriod, Guard, MeasurementUnit, Polarity};
+
+/// Keywords that open a declaration.
+const DECLARATION_KEYWORDS: &[&str] = &["clause", "budget"];
+
+/// A byte range `(start, end)` within a declaration.
+type Range = (usize, usize);
+
+/// A lexical token of a declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lexeme<'a> {
+    /// A bare word: keyword, name, number or unit.
+    Word(&'a str),
+    /// A double-quoted string, without the quotes.
+    Text(&'a str),
+    /// One of `{`, `}`, `;` and `/`.
+    Symbol(char),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    lexeme: Lexeme<'a>,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Token<'a> {
+    /// Returns the word or string the token holds.
+    fn value(&self) -> Option<&'a str> {
+        match self.lexeme {
+            Lexeme::Word(value) | Lexeme::Text(value) => Some(value),
+            Lexeme::Symbol(_) => None,
+        }
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        self.lexeme == Lexeme::Word(word)
+    }
+}
+
+/// A syntax error, with the byte range it covers in the declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DslError {
+    pub message: String,
+    pub range: Range,
+}
+
+impl DslError {
+    fn new(message: String, range: Range) -> Self {
+        DslError { message, range }
+    }
+}
+
+/// A principal named by a declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DeclaredPrincipal {
+    pub name: String,
+    pub role: AuthorityRole,
+    pub range: Range,
+}
+
+/// What a declaration governs.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Rule {
+    /// `must`, `must not` or `can` perform `action` on `resource`.
+    Clause { polarity: Polarity, action: String, resource: String },
+    /// Spending on `subject` must not exceed `ceiling` per `period`.
+    Budget { subject: String, ceiling: f64, unit: MeasurementUnit, period: BudgetPeriod },
+}
+
+/// A parsed `clause` or `budget` declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Declaration {
+    pub name: String,
+    pub name_range: Range,
+    pub principals: Vec<DeclaredPrincipal>,
+    pub rule: Rule,
+    pub guard: Option<Guard>,
+}
+
+impl Declaration {
+    /// Returns the polarity of the declaration; budgets prohibit exceeding their ceiling.
+    pub fn polarity(&self) -> Polarity {
+        match &self.rule {
+            Rule::Clause { polarity, .. } => *polarity,
+            Rule::Budget { .. } => Polarity::Prohibition,
+        }
+    }
+}
+
+/// Splits text into tokens, starting at byte `pos`.
+struct Lexer<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, DslError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip whitespace and comments
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with("//") {
+                break;
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+
+        let start = self.pos;
+        let c = self.text[start..].chars().next()?;
+        let token = |lexeme, end| Token { lexeme, start, end };
+        Some(match c {
+            '{' | '}' | ';' | '/' => {
+                self.pos += 1;
+                Ok(token(Lexeme::Symbol(c), self.pos))
+            }
+            '"' => {
+                let body = start + 1;
+                let line_end = self.text[body..].find('\n').map_or(self.text.len(), |i| body + i);
+                match self.text[body..line_end].find('"') {
+                    Some(close) => {
+                        self.pos = body + close + 1;
+                        Ok(token(Lexeme::Text(&self.text[body..body + close]), self.pos))
+                    }
+                    None => {
+                        self.pos = line_end;
+                        Err(DslError::new("unterminated string".to_string(), (start, line_end)))
+                    }
+                }
+            }
+            _ => {
+                let len = self.text[start..]
+                    .find(|c: char| c.is_whitespace() || matches!(c, '{' | '}' | ';' | '/' | '"'))
+                    .unwrap_or(self.text.len() - start);
+                self.pos += len;
+                Ok(token(Lexeme::Word(&self.text[start..self.pos]), self.pos))
+            }
+        })
+    }
+}
+
+/// Finds the declarations in a policy. Returns the byte range of each, from its
+/// keyword to its closing brace, or to the end of the text if it is never closed.
+pub(crate) fn find_declarations(text: &str) -> Vec<Range> {
+    let mut found = Vec::new();
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let rest = &text[pos..];
+        let start = pos + (rest.len() - rest.trim_start().len());
+        match declaration_end(text, start) {
+            Some(end) => {
+                found.push((start, end));
+                pos = end;
+            }
+            None => pos = rest.find('\n').map_or(text.len(), |i| pos + i + 1),
+        }
+    }
+
+    found
+}
+
+/// Returns the end of the declaration starting at `start`, if one does.
+fn declaration_end(text: &str, start: usize) -> Option<usize> {
+    let mut lexer = Lexer { text, pos: start };
+    let header: Vec<Token<'_>> = lexer.by_ref().take(3).collect::<Result<_, _>>().ok()?;
+    match header.as_slice() {
+        [keyword, name, open]
+            if DECLARATION_KEYWORDS.iter().any(|k| keyword.is_word(k))
+                && matches!(name.lexeme, Lexeme::Word(_))
+                && open.lexeme == Lexeme::Symbol('{') => {}
+        _ => return None,
+    }
+    let close = lexer.find_map(|token| match token {
+        Ok(Token { lexeme: Lexeme::Symbol('}'), end, .. }) => Some(end),
+        _ => None,
+    });
+    Some(close.unwrap_or(text.len()))
+}
+
+/// Parses a single declaration, as found by `find_declarations`.
+pub(crate) fn parse(source: &str) -> Result<Declaration, DslError> {
+    let tokens: Vec<Token<'_>> = Lexer { text: source, pos: 0 }.collect::<Result<_, _>>()?;
+    let (keyword, name) = match tokens.as_slice() {
+        [keyword, name, ..] => (keyword.value().unwrap_or_default(), *name),
+        _ => return Err(DslError::new("expected a declaration".to_string(), (0, source.len()))),
+    };
+    let is_budget = keyword == "budget";
+
+    let mut principals = Vec::new();
+    let mut rule: Option<Rule> = None;
+    let mut subject: Option<String> = None;
+    let mut guards = Vec::new();
+
+    // Statements run from their keyword to ';'
+    let mut k = 3;
+    loop {
+        let first = match tokens.get(k) {
+            Some(token) if token.lexeme == Lexeme::Symbol('}') => break,
+            Some(token) => *token,
+            None => return Err(DslError::new("expected '}'".to_string(), (source.len(), source.len()))),
+        };
+        let end = k + tokens[k..].iter()
+            .position(|t| matches!(t.lexeme, Lexeme::Symbol(';') | Lexeme::Symbol('}')))
+            .filter(|&len| tokens[k + len].lexeme == Lexeme::Symbol(';'))
+            .ok_or_else(|| DslError::new("expected ';'".to_string(), (first.start, tokens[tokens.len() - 1].end)))?;
+        let args = &tokens[k + 1..end];
+        let range = (first.start, tokens[end].end);
+        k = end + 1;
+
+        let statement = match first.lexeme {
+            Lexeme::Word(statement) => statement,
+            _ => return Err(DslError::new("expected a statement".to_string(), range)),
+        };
+        match statement {
+            "principal" => principals.push(parse_principal(args, range)?),
+            "when" | "unless" => {
+                let condition = match args {
+                    [condition] => condition.value().unwrap_or_default().to_lowercase(),
+                    _ => return Err(DslError::new(format!("'{}' expects one condition", statement), range)),
+                };
+                let guard = Guard::Condition { name: condition };
+                guards.push(if statement == "unless" { Guard::Not { guard: Box::new(guard) } } else { guard });
+            }
+            "must" | "can" if !is_budget => {
+                if rule.is_some() {
+                    return Err(DslError::new("a clause declares one rule".to_string(), range));
+                }
+                rule = Some(parse_rule(statement, args, range)?);
+            }
+            "subject" if is_budget => match args {
+                [value] if value.value().is_some() => subject = value.value().map(str::to_string),
+                _ => return Err(DslError::new("'subject' expects one name".to_string(), range)),
+            },
+            "ceiling" if is_budget => {
+                if rule.is_some() {
+                    return Err(DslError::new("a budget declares one ceiling".to_string(), range));
+                }
+                rule = Some(parse_ceiling(args, range)?);
+            }
+            "must" | "can" | "subject" | "ceiling" => {
+                return Err(DslError::new(format!("'{}' is not allowed in a {}", statement, keyword), range));
+            }
+            other => return Err(DslError::new(format!("unknown statement '{}'", other), range)),
+        }
+    }
+
+    let name_range = (name.start, name.end);
+    let name = name.value().unwrap_or_default().to_string();
+    if principals.is_empty() {
+        return Err(DslError::new(format!("{} '{}' names no principal", keyword, name), name_range));
+    }
+    let rule = match rule {
+        Some(Rule::Budget { ceiling, unit, period, .. }) => {
+            Rule::Budget { subject: subject.unwrap_or_else(|| name.clone()), ceiling, unit, period }
+        }
+        Some(rule) => rule,
+        None if is_budget => return Err(DslError::new(format!("budget '{}' sets no ceiling", name), name_range)),
+        None => return Err(DslError::new(format!("clause '{}' states no rule", name), name_range)),
+    };
+    let guard = match guards.len() {
+        0 => None,
+        1 => guards.pop(),
+        _ => Some(Guard::All { guards }),
+    };
+
+    Ok(Declaration { name, name_range, principals, rule, guard })
+}
+
+/// Parses `principal NAME [as ROLE]`.
+fn parse_principal(args: &[Token<'_>], range: Range) -> Result<DeclaredPrincipal, DslError> {
+    let (name, role) = match args {
+        [name] => (*name, AuthorityRole::Actor),
+        [name, as_, role] if as_.is_word("as") => {
+            let role = match role.value().unwrap_or_default() {
+                "actor" => AuthorityRole::Actor,
+                "approver" => AuthorityRole::Approver,
+                "delegate" => AuthorityRole::Delegate,
+                "auditor" => AuthorityRole::Auditor,
+                other => return Err(DslError::new(format!("unknown role '{}'", other), (role.start, role.end))),
+            };
+            (*name, role)
+        }
+        _ => return Err(DslError::new("'principal' expects NAME [as ROLE]".to_string(), range)),
+    };
+    match name.value() {
+        Some(value) => Ok(DeclaredPrincipal { name: value.to_string(), role, range: (name.start, name.end) }),
+        None => Err(DslError::new("'principal' expects a name".to_string(), range)),
+    }
+}
+
+/// Parses `must [not] VERB OBJECT` or `can VERB OBJECT`.
+fn parse_rule(statement: &str, args: &[Token<'_>], range: Range) -> Result<Rule, DslError> {
+    let (polarity, args) = match (statement, args) {
+        ("must", [not, rest @ ..]) if not.is_word("not") => (Polarity::Prohibition, rest),
+        ("must", args) => (Polarity::Obligation, args),
+        (_, args) => (Polarity::Permission, args),
+    };
+    match args {
+        [verb, object] if matches!(verb.lexeme, Lexeme::Word(_)) && object.value().is_some() => {
+            let words: Vec<String> = object.value().unwrap_or_default().split_whitespace().map(str::to_lowercase).collect();
+            Ok(Rule::Clause {
+                polarity,
+                action: verb.value().unwrap_or_default().to_lowercase(),
+                resource: words.join("_"),
+            })
+        }
+        _ => Err(DslError::new(format!("'{}' expects VERB OBJECT", statement), range)),
+    }
+}
+
+/// Parses `ceiling AMOUNT UNIT/PERIOD` or `ceiling AMOUNT UNIT per PERIOD`.
+fn parse_ceiling(args: &[Token<'_>], range: Range) -> Result<Rule, DslError> {
+    let usage = || DslError::new("'ceiling' expects AMOUNT UNIT/PERIOD".to_string(), range);
+    let (amount, unit, period) = match args {
+        [amount, unit, separator, period] if separator.lexeme == Lexeme::Symbol('/') || separator.is_word("per") => {
+            (amount, unit, period)
+        }
+        _ => return Err(usage()),
+    };
+    let ceiling = amount.value().and_then(parse_quantity)
+        .ok_or_else(|| DslError::new("expected an amount".to_string(), (amount.start, amount.end)))?;
+    let unit_name = unit.value().unwrap_or_default();
+    let unit = MeasurementUnit::ALL.iter().copied()
+        .find(|candidate| candidate.as_str().eq_ignore_ascii_case(unit_name))
+        .ok_or_else(|| DslError::new(format!("unknown unit '{}'", unit_name), (unit.start, unit.end)))?;
+    let period_name = period.value().unwrap_or_default().to_lowercase();
+    let period = BudgetPeriod::from_word(&period_name)
+        .ok_or_else(|| DslError::new(format!("unknown budget period '{}'", period_name), (period.start, period.end)))?;
+
+    Ok(Rule::Budget { subject: String::new(), ceiling, unit, period })
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_find_declarations_among_text() {
+        let text = "SYSTEM must log access.\nclause access-log {\n  principal SYSTEM; // the platform\n  must log \"access } log\";\n}\nUSER must audit logs. budget x { }";
+        let found = find_declarations(text);
+        assert_eq!(found.len(), 1);
+        assert_eq!(&text[found[0].0..found[0].1], "clause access-log {\n  principal SYSTEM; // the platform\n  must log \"access } log\";\n}");
+    }
+
+    #[test]
+    fn test_parse_clause_and_budget() {
+        let clause = parse("clause no-export {\n principal ROLE:auditor as auditor;\n principal SYSTEM;\n must not export \"customer records\";\n unless incident_declared;\n}").unwrap();
+        assert_eq!(clause.name, "no-export");
+        assert_eq!(clause.principals.iter().map(|p| (p.name.as_str(), p.role)).collect::<Vec<_>>(), vec![
+            ("ROLE:auditor", AuthorityRole::Auditor),
+            ("SYSTEM", AuthorityRole::Actor),
+        ]);
+        assert_eq!(clause.rule, Rule::Clause {
+            polarity: Polarity::Prohibition,
+            action: "export".to_string(),
+            resource: "customer_records".to_string(),
+        });
+        assert_eq!(clause.guard.unwrap().to_string(), "!incident_declared");
+
+        let budget = parse("budget api { ceiling 1,000 usd per month; principal SERVICE; }").unwrap();
+        assert_eq!(budget.rule, Rule::Budget {
+            subject: "api".to_string(),
+            ceiling: 1000.0,
+            unit: MeasurementUnit::Usd,
+            period: BudgetPeriod::Monthly,
+        });
+        assert_eq!(budget.polarity(), Polarity::Prohibition);
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_problem() {
+        let source = "clause a { principal SYSTEM; must log \"access\" }";
+        let error = parse(source).unwrap_err();
+        assert_eq!(error.message, "expected ';'");
+        assert_eq!(&source[error.range.0..error.range.1], "must log \"access\" }");
+
+        let source = "budget api { principal SERVICE; ceiling 10 dollars/month; }";
+        let error = parse(source).unwrap_err();
+        assert_eq!(error.message, "unknown unit 'dollars'");
+        assert_eq!(&source[error.range.0..error.range.1], "dollars");
+
+        let error = parse("clause a { principal SYSTEM; ceiling 1 USD/day; }").unwrap_err();
+        assert_eq!(error.message, "'ceiling' is not allowed in a clause");
+        assert_eq!(parse("clause a { must log x; }").unwrap_err().message, "clause 'a' names no principal");
+    }
+}
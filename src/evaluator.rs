@@ -0,0 +1,287 @@
+//! Runtime evaluation of execution, authorization and cost events against the
+//! artifacts of a successful compilation.
+//!
+//! Each violation names the artifact that was broken, carries its `failure_signal`
+//! and traces back to the source clause through the result's `traceability_map`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ledger::{AlertLevel, CostLedger, UsageRecord};
+use crate::{CompilationResult, ExecutionEvent, Guard, MeasurementUnit, Polarity, RevocationTrigger, ZTAuthority};
+
+/// A principal attempting `action` on `resource`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuthorizationEvent {
+    pub principal: String,
+    pub action: String,
+    pub resource: String,
+}
+
+/// Usage of `amount` units charged to `subject`, optionally at `timestamp` (Unix
+/// seconds). Events without a timestamp count towards the budget window starting
+/// at the epoch.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CostEvent {
+    pub subject: String,
+    pub amount: f64,
+    pub unit: MeasurementUnit,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timestamp: Option<u64>,
+}
+
+/// One entry of an event stream. In JSON the kind is given by a `"type"` tag, e.g.
+/// `{"type": "cost", "subject": "api", "amount": 12.5, "unit": "USD"}`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum PolicyEvent {
+    Execution(ExecutionEvent),
+    Authorization(AuthorizationEvent),
+    Cost(CostEvent),
+}
+
+/// The kind of artifact a violation refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ArtifactKind {
+    Dio,
+    Zt,
+    Icae,
+}
+
+/// A broken artifact, traced back to its clause.
+///
+/// `event_index` is the position of the offending event in the stream, or `None`
+/// for DIO invariants that were only found unmet when the stream finished.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Violation {
+    pub kind: ArtifactKind,
+    pub artifact_id: String,
+    pub failure_signal: String,
+    pub clause_index: usize,
+    pub clause_id: String,
+    pub event_index: Option<usize>,
+    pub reason: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ({}): {}", self.failure_signal, self.artifact_id, self.clause_id, self.reason)
+    }
+}
+
+/// Checks a stream of events against a `CompilationResult`.
+///
+/// - Execution events extend the trace. A DIO invariant is reported as soon as the
+///   trace breaks it for good (a forbidden event, a missing field, an event out of
+///   order); invariants still unmet when the stream ends are reported by `finish`.
+/// - Authorization events are checked against the authorities whose scope matches
+///   the action and resource. The principal must hold an authority for that scope
+///   that is not a prohibition and has not been revoked (see `revoke`).
+/// - Cost events are recorded in a `CostLedger`. A constraint is reported once its
+///   ceiling is exceeded within a budget window; `per_request` ceilings apply to
+///   each event on its own.
+///
+/// Each DIO invariant is reported at most once, and each cost constraint at most
+/// once per window; every unauthorized event is reported.
+///
+/// Artifacts with a `guard` apply only while it holds over the active conditions
+/// given to `with_conditions`; by default no condition is active.
+#[derive(Debug, Clone)]
+pub struct Evaluator<'a> {
+    result: &'a CompilationResult,
+    conditions: BTreeSet<String>,
+    revocations: Vec<RevocationTrigger>,
+    trace: Vec<ExecutionEvent>,
+    ledger: CostLedger,
+    reported: BTreeSet<String>,
+    events_seen: usize,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Creates an evaluator with an empty trace.
+    pub fn new(result: &'a CompilationResult) -> Self {
+        Evaluator {
+            result,
+            conditions: BTreeSet::new(),
+            revocations: Vec::new(),
+            trace: Vec::new(),
+            ledger: CostLedger::new(&result.icae_constraints),
+            reported: BTreeSet::new(),
+            events_seen: 0,
+        }
+    }
+
+    /// Sets the names of the active conditions guards are checked against, such as
+    /// `incident_declared`.
+    pub fn with_conditions<I, S>(mut self, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.conditions = conditions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Records that `trigger` occurred, such as a role change or 30 days passing.
+    /// Authorities it revokes no longer authorize later events.
+    pub fn revoke(&mut self, trigger: RevocationTrigger) {
+        self.revocations.push(trigger);
+    }
+
+    /// Evaluates every event and the end of the stream in one call.
+    pub fn evaluate<'e, I>(result: &'a CompilationResult, events: I) -> Vec<Violation>
+    where
+        I: IntoIterator<Item = &'e PolicyEvent>,
+    {
+        let mut evaluator = Evaluator::new(result);
+        let mut violations: Vec<Violation> = events.into_iter().flat_map(|event| evaluator.observe(event)).collect();
+        violations.extend(evaluator.finish());
+        violations
+    }
+
+    /// Records one event and returns the violations it causes.
+    pub fn observe(&mut self, event: &PolicyEvent) -> Vec<Violation> {
+        let index = self.events_seen;
+        self.events_seen += 1;
+
+        match event {
+            PolicyEvent::Execution(execution) => {
+                self.trace.push(execution.clone());
+                self.check_invariants(Some(index))
+            }
+            PolicyEvent::Authorization(request) => self.check_authorization(request, index).into_iter().collect(),
+            PolicyEvent::Cost(cost) => self.check_cost(cost, index),
+        }
+    }
+
+    /// Ends the stream and returns the DIO invariants the full trace leaves unmet.
+    pub fn finish(mut self) -> Vec<Violation> {
+        self.check_invariants(None)
+    }
+
+    fn check_invariants(&mut self, event_index: Option<usize>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for invariant in &self.result.dio_invariants {
+            if !self.applies(&invariant.guard) {
+                continue;
+            }
+            let broken = match event_index {
+                Some(_) => invariant.predicate.is_violated_by_prefix(&self.trace),
+                None => !invariant.predicate.evaluate(&self.trace),
+            };
+            if broken && self.reported.insert(invariant.id.clone()) {
+                violations.push(self.violation(
+                    ArtifactKind::Dio,
+                    &invariant.id,
+                    &invariant.failure_signal,
+                    invariant.clause_index,
+                    event_index,
+                    format!("trace violates {}", invariant.predicate),
+                ));
+            }
+        }
+        violations
+    }
+
+    fn check_authorization(&self, request: &AuthorizationEvent, index: usize) -> Option<Violation> {
+        let governing: Vec<_> = self.result.zt_authority_graph.iter()
+            .filter(|auth| {
+                self.applies(&auth.guard)
+                    && auth.scope.action.eq_ignore_ascii_case(&request.action)
+                    && auth.scope.resource.eq_ignore_ascii_case(&request.resource)
+            })
+            .collect();
+        let (revoked, active): (Vec<&ZTAuthority>, Vec<&ZTAuthority>) = governing.iter()
+            .filter(|auth| auth.identity.eq_ignore_ascii_case(&request.principal))
+            .partition(|auth| self.is_revoked(auth));
+
+        if active.iter().any(|auth| auth.polarity != Polarity::Prohibition) {
+            return None;
+        }
+        let (authority, reason) = match (active.first(), revoked.first()) {
+            (Some(denied), _) => (*denied, format!("{} is denied {}", request.principal, denied.scope)),
+            (None, Some(revoked)) => (*revoked, format!("{}'s authority for {} was revoked", request.principal, revoked.scope)),
+            (None, None) => (*governing.first()?, format!("{} holds no authority for {}:{}", request.principal, request.action, request.resource)),
+        };
+        Some(self.violation(
+            ArtifactKind::Zt,
+            &authority.id,
+            &authority.failure_signal,
+            authority.clause_index,
+            Some(index),
+            reason,
+        ))
+    }
+
+    fn is_revoked(&self, authority: &ZTAuthority) -> bool {
+        self.revocations.iter().any(|trigger| authority.is_revoked_by(trigger))
+    }
+
+    fn check_cost(&mut self, cost: &CostEvent, index: usize) -> Vec<Violation> {
+        let record = UsageRecord {
+            subject: cost.subject.clone(),
+            amount: cost.amount,
+            unit: cost.unit,
+            timestamp: cost.timestamp.unwrap_or(0),
+        };
+        let alerts = self.ledger.record(&record);
+        let constraints = &self.result.icae_constraints;
+        alerts
+            .into_iter()
+            .filter(|alert| alert.level == AlertLevel::Exceeded)
+            .filter(|alert| {
+                constraints.iter()
+                    .filter(|constraint| constraint.id == alert.constraint_id)
+                    .all(|constraint| self.applies(&constraint.guard))
+            })
+            .map(|alert| {
+                self.violation(
+                    ArtifactKind::Icae,
+                    &alert.constraint_id,
+                    &alert.failure_signal,
+                    alert.clause_index,
+                    Some(index),
+                    format!("{} {} exceeds ceiling {} {}", alert.total, cost.unit, alert.ceiling, cost.unit),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns true if an artifact with `guard` applies under the active conditions.
+    fn applies(&self, guard: &Option<Guard>) -> bool {
+        guard.iter().all(|guard| guard.holds(&self.conditions))
+    }
+
+    fn violation(
+        &self,
+        kind: ArtifactKind,
+        artifact_id: &str,
+        failure_signal: &str,
+        clause_index: usize,
+        event_index: Option<usize>,
+        reason: String,
+    ) -> Violation {
+        let clause_id = self.result.traceability_map.iter()
+            .find(|entry| entry.clause_index == clause_index)
+            .map(|entry| entry.clause_id.clone())
+            .unwrap_or_else(|| format!("clause_{}", clause_index));
+        Violation {
+            kind,
+            artifact_id: artifact_id.to_string(),
+            failure_signal: failure_signal.to_string(),
+            clause_index,
+            clause_id,
+            event_index,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Typed assumptions and exclusions stated by policy clauses.
+//!
+//! "Assume(s) X" records X as an assumption. "except X" and "excluding X" exclude
+//! principals or resources from the clause's artifacts, and "unless X" / "except
+//! when X" exclude the conditions under which the clause does not apply.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::tokenizer::{is_inflection_of, tokenize};
+use crate::{Guard, Span, SCOPE_BOUNDARIES, SCOPE_FILLER};
+
+/// A byte range `(start, end)` within a clause.
+type Range = (usize, usize);
+
+/// A premise a clause takes for granted, e.g. "network is secure" in "Assumes network is secure".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Assumption {
+    pub clause_index: usize,
+    pub statement: String,
+    pub span: Span,
+}
+
+/// What an exclusion removes from its clause's artifacts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ExclusionTarget {
+    /// A named principal the clause does not apply to ("except ROLE:auditor").
+    Principal { identity: String },
+    /// A resource the clause does not apply to ("except health checks" → `health_checks`).
+    Resource { name: String },
+    /// A condition under which the clause does not apply ("unless an incident is declared").
+    Condition { guard: Guard },
+}
+
+/// An exception stated by a clause, with the span of its phrase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Exclusion {
+    pub clause_index: usize,
+    pub target: ExclusionTarget,
+    pub span: Span,
+}
+
+/// Returns the byte range of the statement a clause assumes: everything after
+/// "assume", "assumes" or "assuming" (and an optional "that").
+pub(crate) fn find_assumption(clause: &str) -> Option<Range> {
+    let tokens = tokenize(clause);
+    let k = tokens.iter().position(|t| is_inflection_of(&t.lower, "assume"))?;
+    let start = tokens[k + 1..].iter().find(|t| t.lower != "that")?.offset;
+    Some((start, clause.len()))
+}
+
+/// Finds the "except X" or "excluding X" phrase of a clause. Returns the byte
+/// range of the phrase and of each excluded target; targets are separated by
+/// commas, "and" or "or". A leading phrase ends at the first comma.
+pub(crate) fn find_exclusion_targets(clause: &str) -> Option<(Range, Vec<Range>)> {
+    let tokens = tokenize(clause);
+    let k = tokens.iter().enumerate().position(|(k, t)| {
+        let conditional = matches!(tokens.get(k + 1), Some(next) if next.lower == "when" || next.lower == "if");
+        (t.lower == "except" && !conditional) || is_inflection_of(&t.lower, "exclude")
+    })?;
+    let first = k + 1 + tokens[k + 1..]
+        .iter()
+        .take_while(|t| SCOPE_FILLER.contains(&t.lower.as_str()) || t.lower == "by")
+        .count();
+
+    let mut targets: Vec<Range> = Vec::new();
+    let mut previous_end: Option<usize> = None;
+    for token in &tokens[first.min(tokens.len())..] {
+        let separated = match previous_end {
+            Some(end) if clause[end..token.offset].contains(',') => {
+                if k == 0 {
+                    break;
+                }
+                true
+            }
+            Some(_) => false,
+            None => true,
+        };
+        if token.lower == "and" || token.lower == "or" {
+            previous_end = None;
+            continue;
+        }
+        if SCOPE_BOUNDARIES.contains(&token.lower.as_str()) {
+            break;
+        }
+        match targets.last_mut() {
+            Some(target) if !separated => target.1 = token.end(),
+            _ => targets.push((token.offset, token.end())),
+        }
+        previous_end = Some(token.end());
+    }
+
+    let end = targets.last()?.1;
+    Some(((tokens[k].offset, end), targets))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_assumption_statement() {
+        let clause = "Assumes that the network is secure";
+        let (start, end) = find_assumption(clause).unwrap();
+        assert_eq!(&clause[start..end], "the network is secure");
+        assert!(find_assumption("SYSTEM must log access").is_none());
+    }
+
+    #[test]
+    fn test_exclusion_targets() {
+        let clause = "SYSTEM must log access except health checks, ROLE:auditor and SERVICE";
+        let ((start, end), targets) = find_exclusion_targets(clause).unwrap();
+        assert_eq!(&clause[start..end], "except health checks, ROLE:auditor and SERVICE");
+        let targets: Vec<&str> = targets.iter().map(|(start, end)| &clause[*start..*end]).collect();
+        assert_eq!(targets, vec!["health checks", "ROLE:auditor", "SERVICE"]);
+    }
+
+    #[test]
+    fn test_leading_exclusion_and_conditional_except() {
+        let clause = "Except for testing, SYSTEM must log access";
+        let (_, targets) = find_exclusion_targets(clause).unwrap();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(&clause[targets[0].0..targets[0].1], "testing");
+        assert!(find_exclusion_targets("SYSTEM must log access except when testing").is_none());
+    }
+}
@@ -0,0 +1,215 @@
+//! Guards: the conditions under which a clause applies.
+//!
+//! "if X" and "when X" make a clause apply only while X holds; "unless X" and
+//! "except when X" make it apply only while X does not. A condition phrase leads
+//! the clause up to the first comma ("If an incident is declared, ...") or, without
+//! one, up to the principal the clause goes on to name ("When an incident is
+//! declared SYSTEM must ..."); or it trails the clause to the end ("... unless the
+//! request is internal"). Conditions are named by
+//! their content words joined with underscores, so "an incident is declared" is
+//! `incident_declared`; "and", "or" and "not" combine them.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::tokenizer::tokenize;
+use crate::{PrincipalRegistry, RevocationTrigger};
+
+/// Words that open a condition the clause applies under.
+const WHEN_MARKERS: &[&str] = &["if", "when"];
+
+/// Words that open a condition the clause does not apply under.
+const UNLESS_MARKERS: &[&str] = &["unless"];
+
+/// Modals that open the predicate of the clause a leading condition precedes.
+const CLAUSE_MODALS: &[&str] = &["must", "shall", "will", "can", "cannot", "should", "may"];
+
+/// Words dropped from condition names.
+const CONDITION_FILLER: &[&str] = &[
+    "the", "a", "an", "any", "its", "their", "is", "are", "be", "been", "was", "were", "has", "have", "had",
+];
+
+/// A condition expression over named conditions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Guard {
+    /// Holds while the named condition, such as `incident_declared`, is active.
+    Condition { name: String },
+    /// Holds while `guard` does not.
+    Not { guard: Box<Guard> },
+    /// Holds while every guard does.
+    All { guards: Vec<Guard> },
+    /// Holds while at least one guard does.
+    Any { guards: Vec<Guard> },
+}
+
+impl Guard {
+    /// Returns true if the guard holds given the names of the active conditions.
+    pub fn holds(&self, active: &BTreeSet<String>) -> bool {
+        match self {
+            Guard::Condition { name } => active.contains(name),
+            Guard::Not { guard } => !guard.holds(active),
+            Guard::All { guards } => guards.iter().all(|g| g.holds(active)),
+            Guard::Any { guards } => guards.iter().any(|g| g.holds(active)),
+        }
+    }
+
+    /// Parses a condition such as "the request is internal or an incident is
+    /// declared". "and" binds tighter than "or".
+    fn parse(condition: &str) -> Option<Self> {
+        let tokens = tokenize(condition);
+        let mut alternatives = Vec::new();
+        for alternative in tokens.split(|t| t.lower == "or") {
+            let mut terms = Vec::new();
+            for term in alternative.split(|t| t.lower == "and") {
+                let negated = term.iter().any(|t| t.lower == "not" || t.lower == "no");
+                let words: Vec<&str> = term.iter()
+                    .map(|t| t.lower.as_str())
+                    .filter(|word| !CONDITION_FILLER.contains(word) && *word != "not" && *word != "no")
+                    .collect();
+                if words.is_empty() {
+                    return None;
+                }
+                let condition = Guard::Condition { name: words.join("_") };
+                terms.push(if negated { Guard::Not { guard: Box::new(condition) } } else { condition });
+            }
+            alternatives.push(if terms.len() == 1 { terms.remove(0) } else { Guard::All { guards: terms } });
+        }
+        Some(if alternatives.len() == 1 { alternatives.remove(0) } else { Guard::Any { guards: alternatives } })
+    }
+}
+
+impl fmt::Display for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |guards: &[Guard], separator: &str| {
+            guards.iter().map(|g| g.to_string()).collect::<Vec<_>>().join(separator)
+        };
+        match self {
+            Guard::Condition { name } => write!(f, "{}", name),
+            Guard::Not { guard } => write!(f, "!{}", guard),
+            Guard::All { guards } => write!(f, "({})", join(guards, " && ")),
+            Guard::Any { guards } => write!(f, "({})", join(guards, " || ")),
+        }
+    }
+}
+
+/// Returns whether a clause opens with a condition marker ("If ...", "When ...",
+/// "Unless ...").
+pub(crate) fn leads_with_condition(clause: &str) -> bool {
+    let marker = |word: &str| WHEN_MARKERS.contains(&word) || UNLESS_MARKERS.contains(&word);
+    matches!(tokenize(clause).first(), Some(first) if marker(&first.lower))
+}
+
+/// Finds the condition phrase of a clause. Returns the guard and the byte range of
+/// the phrase, including its marker and any comma ending a leading phrase. A
+/// leading phrase without a comma ends at the last principal of `principals`
+/// named before the clause's modal; with neither, the clause has no guard.
+///
+/// Clauses that mention revocation keep their conditions as revocation triggers
+/// ("revoke access when budget exceeded") and have no guard.
+pub(crate) fn find_guard(clause: &str, principals: &PrincipalRegistry) -> Option<(Guard, (usize, usize))> {
+    if !RevocationTrigger::from_clause(clause).is_empty() {
+        return None;
+    }
+    let tokens = tokenize(clause);
+    let (k, negated, skip) = tokens.iter().enumerate().find_map(|(k, t)| {
+        let next = tokens.get(k + 1).map(|t| t.lower.as_str());
+        if WHEN_MARKERS.contains(&t.lower.as_str()) {
+            Some((k, false, 1))
+        } else if UNLESS_MARKERS.contains(&t.lower.as_str()) {
+            Some((k, true, 1))
+        } else if t.lower == "except" && next.iter().any(|next| WHEN_MARKERS.contains(next)) {
+            Some((k, true, 2))
+        } else {
+            None
+        }
+    })?;
+    let condition_start = tokens.get(k + skip)?.offset;
+
+    // A leading phrase runs to the first comma or the clause's principal, a
+    // trailing one to the end
+    let (condition_end, phrase_end) = if k == 0 {
+        let modal = tokens.iter()
+            .find(|t| t.offset > condition_start && CLAUSE_MODALS.contains(&t.lower.as_str()))
+            .map_or(clause.len(), |t| t.offset);
+        match clause[condition_start..modal].find(',') {
+            Some(comma) => (condition_start + comma, condition_start + comma + 1),
+            None => {
+                let subject = principals.mentions(clause)
+                    .into_iter()
+                    .map(|((start, _), _)| start)
+                    .rfind(|start| *start > condition_start && *start < modal)?;
+                (subject, subject)
+            }
+        }
+    } else {
+        (clause.len(), clause.len())
+    };
+
+    let guard = Guard::parse(&clause[condition_start..condition_end])?;
+    let guard = if negated { Guard::Not { guard: Box::new(guard) } } else { guard };
+    Some((guard, (tokens[k].offset, phrase_end)))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn active(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn find(clause: &str) -> Option<(Guard, (usize, usize))> {
+        find_guard(clause, &PrincipalRegistry::new())
+    }
+
+    #[test]
+    fn test_leading_and_trailing_guards() {
+        let (guard, range) = find("If an incident is declared, SYSTEM must log access").unwrap();
+        assert_eq!(guard, Guard::Condition { name: "incident_declared".to_string() });
+        assert_eq!(range, (0, 27));
+
+        let (guard, range) = find("SYSTEM must log access except when the request is internal").unwrap();
+        assert_eq!(guard.to_string(), "!request_internal");
+        assert_eq!(range, (23, 58));
+    }
+
+    #[test]
+    fn test_compound_conditions() {
+        let (guard, _) = find("SYSTEM must log access when an incident is declared and the user is not admin or audit mode is on").unwrap();
+        assert_eq!(guard.to_string(), "((incident_declared && !user_admin) || audit_mode_on)");
+        assert!(guard.holds(&active(&["incident_declared"])));
+        assert!(!guard.holds(&active(&["incident_declared", "user_admin"])));
+        assert!(guard.holds(&active(&["audit_mode_on"])));
+    }
+
+    #[test]
+    fn test_leading_guard_without_comma_ends_at_principal() {
+        let clause = "When an incident is declared SYSTEM must log access";
+        let (guard, range) = find(clause).unwrap();
+        assert_eq!(guard.to_string(), "incident_declared");
+        assert_eq!(&clause[range.0..range.1], "When an incident is declared ");
+
+        let clause = "If the request is external USER must not read payroll";
+        let (guard, range) = find(clause).unwrap();
+        assert_eq!(guard.to_string(), "request_external");
+        assert_eq!(&clause[range.1..], "USER must not read payroll");
+
+        // The last principal before the modal opens the clause
+        let (guard, _) = find("When the system is degraded SYSTEM must deny requests").unwrap();
+        assert_eq!(guard.to_string(), "system_degraded");
+        let registered = PrincipalRegistry::new().with("billing-service", crate::Principal::Service);
+        let (guard, _) = find_guard("Unless audit mode is on billing-service must log access", &registered).unwrap();
+        assert_eq!(guard.to_string(), "!audit_mode_on");
+        assert!(find("When an incident is declared access must be logged by SYSTEM").is_none());
+    }
+
+    #[test]
+    fn test_revocation_conditions_are_not_guards() {
+        assert!(find("Revoke USER access when budget exceeded").is_none());
+    }
+}
@@ -0,0 +1,264 @@
+//! Typed predicates for DIO invariants and the execution events they are checked against.
+//!
+//! A predicate is compiled from a policy clause and evaluated against an execution
+//! trace: an ordered slice of `ExecutionEvent`s. Events are matched by action and,
+//! optionally, resource, both compared case-insensitively.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single event in an execution trace, such as `log` of `access` by `SYSTEM`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExecutionEvent {
+    pub action: String,
+    pub resource: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub principal: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl ExecutionEvent {
+    /// Creates an event with no principal and no fields.
+    pub fn new(action: &str, resource: &str) -> Self {
+        ExecutionEvent {
+            action: action.to_string(),
+            resource: resource.to_string(),
+            principal: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the principal that performed the event.
+    pub fn with_principal(mut self, principal: &str) -> Self {
+        self.principal = Some(principal.to_string());
+        self
+    }
+
+    /// Adds a field to the event, replacing any earlier value.
+    pub fn with_field(mut self, name: &str, value: &str) -> Self {
+        self.fields.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Selects events by action and, when given, resource.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EventPattern {
+    pub action: String,
+    pub resource: Option<String>,
+}
+
+impl EventPattern {
+    /// Returns true if `event` has this pattern's action and resource.
+    pub fn matches(&self, event: &ExecutionEvent) -> bool {
+        event.action.eq_ignore_ascii_case(&self.action)
+            && self.resource.iter().all(|resource| event.resource.eq_ignore_ascii_case(resource))
+    }
+}
+
+impl fmt::Display for EventPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.resource {
+            Some(resource) => write!(f, "{}:{}", self.action, resource),
+            None => write!(f, "{}", self.action),
+        }
+    }
+}
+
+/// One step of an ordered multi-step clause, such as "USER must request access"
+/// in "USER must request access then SYSTEM must approve".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InvariantStep {
+    pub id: String,
+    pub text: String,
+    pub pattern: EventPattern,
+}
+
+/// Step `before` must occur before step `after`; both are step ids.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrecedenceEdge {
+    pub before: String,
+    pub after: String,
+}
+
+/// A checkable condition over an execution trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum InvariantPredicate {
+    /// The number of matching events lies within `min..=max`; a missing bound is unbounded.
+    Count { pattern: EventPattern, min: Option<u64>, max: Option<u64> },
+    /// Every matching event carries all of `fields`.
+    RequiredFields { pattern: EventPattern, fields: Vec<String> },
+    /// Every event matching `after` is preceded by an event matching `before`.
+    Precedes { before: EventPattern, after: EventPattern },
+    /// Every predicate holds.
+    All { predicates: Vec<InvariantPredicate> },
+    /// `predicate` holds over the trace without events performed by any of
+    /// `principals` or concerning any of `resources`. An event concerns a resource
+    /// it acts on or names in a field value, such as a `log` of `requests` with
+    /// `{"kind": "health_checks"}`.
+    Excluding { predicate: Box<InvariantPredicate>, principals: Vec<String>, resources: Vec<String> },
+}
+
+impl InvariantPredicate {
+    /// Returns true if the predicate holds for `trace`.
+    pub fn evaluate(&self, trace: &[ExecutionEvent]) -> bool {
+        match self {
+            InvariantPredicate::Count { pattern, min, max } => {
+                let count = trace.iter().filter(|event| pattern.matches(event)).count() as u64;
+                min.iter().all(|min| count >= *min) && max.iter().all(|max| count <= *max)
+            }
+            InvariantPredicate::RequiredFields { pattern, fields } => trace
+                .iter()
+                .filter(|event| pattern.matches(event))
+                .all(|event| fields.iter().all(|field| event.fields.contains_key(field))),
+            InvariantPredicate::Precedes { before, after } => {
+                let mut seen_before = false;
+                for event in trace {
+                    if after.matches(event) && !seen_before {
+                        return false;
+                    }
+                    seen_before |= before.matches(event);
+                }
+                true
+            }
+            InvariantPredicate::All { predicates } => predicates.iter().all(|p| p.evaluate(trace)),
+            InvariantPredicate::Excluding { predicate, .. } => predicate.evaluate(&self.retained(trace)),
+        }
+    }
+
+    /// Returns true if `trace` already violates the predicate, whatever events follow.
+    /// Minimum counts can still be met later, so they never fail a prefix.
+    pub fn is_violated_by_prefix(&self, trace: &[ExecutionEvent]) -> bool {
+        match self {
+            InvariantPredicate::Count { pattern, max, .. } => {
+                let count = trace.iter().filter(|event| pattern.matches(event)).count() as u64;
+                max.iter().any(|max| count > *max)
+            }
+            InvariantPredicate::RequiredFields { .. } | InvariantPredicate::Precedes { .. } => !self.evaluate(trace),
+            InvariantPredicate::All { predicates } => predicates.iter().any(|p| p.is_violated_by_prefix(trace)),
+            InvariantPredicate::Excluding { predicate, .. } => predicate.is_violated_by_prefix(&self.retained(trace)),
+        }
+    }
+
+    /// Returns the events of `trace` an `Excluding` predicate does not exclude.
+    fn retained(&self, trace: &[ExecutionEvent]) -> Vec<ExecutionEvent> {
+        let (principals, resources) = match self {
+            InvariantPredicate::Excluding { principals, resources, .. } => (principals, resources),
+            _ => return trace.to_vec(),
+        };
+        trace
+            .iter()
+            .filter(|event| {
+                !event.principal.iter().any(|p| principals.iter().any(|excluded| p.eq_ignore_ascii_case(excluded)))
+                    && !resources.iter().any(|excluded| {
+                        event.resource.eq_ignore_ascii_case(excluded)
+                            || event.fields.values().any(|value| value.eq_ignore_ascii_case(excluded))
+                    })
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl fmt::Display for InvariantPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantPredicate::Count { pattern, min, max } => match (min, max) {
+                (Some(min), Some(max)) if min == max => write!(f, "count({}) == {}", pattern, min),
+                (Some(min), Some(max)) => write!(f, "{} <= count({}) <= {}", min, pattern, max),
+                (Some(min), None) => write!(f, "count({}) >= {}", pattern, min),
+                (None, Some(max)) => write!(f, "count({}) <= {}", pattern, max),
+                (None, None) => write!(f, "count({}) >= 0", pattern),
+            },
+            InvariantPredicate::RequiredFields { pattern, fields } => {
+                write!(f, "{} has [{}]", pattern, fields.join(", "))
+            }
+            InvariantPredicate::Precedes { before, after } => write!(f, "{} before {}", before, after),
+            InvariantPredicate::All { predicates } => {
+                let parts: Vec<String> = predicates.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}", parts.join(" && "))
+            }
+            InvariantPredicate::Excluding { predicate, principals, resources } => {
+                let excluded: Vec<&str> = principals.iter().chain(resources).map(String::as_str).collect();
+                write!(f, "({}) except [{}]", predicate, excluded.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn pattern(action: &str, resource: Option<&str>) -> EventPattern {
+        EventPattern { action: action.to_string(), resource: resource.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_count_bounds() {
+        let trace = vec![ExecutionEvent::new("log", "access"), ExecutionEvent::new("LOG", "Access")];
+        let at_least = |min| InvariantPredicate::Count { pattern: pattern("log", Some("access")), min: Some(min), max: None };
+
+        assert!(at_least(2).evaluate(&trace));
+        assert!(!at_least(3).evaluate(&trace));
+        let never = InvariantPredicate::Count { pattern: pattern("log", None), min: None, max: Some(0) };
+        assert!(!never.evaluate(&trace));
+    }
+
+    #[test]
+    fn test_required_fields_and_ordering() {
+        let trace = vec![
+            ExecutionEvent::new("log", "access").with_field("timestamp", "1"),
+            ExecutionEvent::new("export", "reports"),
+        ];
+        let fields = InvariantPredicate::RequiredFields {
+            pattern: pattern("log", None),
+            fields: vec!["timestamp".to_string()],
+        };
+        let ordered = InvariantPredicate::Precedes { before: pattern("log", None), after: pattern("export", None) };
+        let reversed = InvariantPredicate::Precedes { before: pattern("export", None), after: pattern("log", None) };
+
+        assert!(fields.evaluate(&trace));
+        assert!(ordered.evaluate(&trace));
+        assert!(!reversed.evaluate(&trace));
+        assert!(!InvariantPredicate::All { predicates: vec![ordered, reversed] }.evaluate(&trace));
+    }
+
+    #[test]
+    fn test_prefix_violations() {
+        let at_least_one = InvariantPredicate::Count { pattern: pattern("log", None), min: Some(1), max: None };
+        let never = InvariantPredicate::Count { pattern: pattern("log", None), min: None, max: Some(0) };
+        let trace = vec![ExecutionEvent::new("log", "access")];
+
+        assert!(!at_least_one.is_violated_by_prefix(&[]));
+        assert!(!never.is_violated_by_prefix(&[]));
+        assert!(never.is_violated_by_prefix(&trace));
+    }
+
+    #[test]
+    fn test_excluded_events_are_ignored() {
+        let never = InvariantPredicate::Count { pattern: pattern("export", None), min: None, max: Some(0) };
+        let excluding = InvariantPredicate::Excluding {
+            predicate: Box::new(never),
+            principals: vec!["ROLE:auditor".to_string()],
+            resources: vec!["health_checks".to_string()],
+        };
+
+        assert!(excluding.evaluate(&[
+            ExecutionEvent::new("export", "payroll").with_principal("role:auditor"),
+            ExecutionEvent::new("export", "health_checks"),
+        ]));
+        assert!(!excluding.evaluate(&[ExecutionEvent::new("export", "payroll").with_principal("USER")]));
+        assert_eq!(excluding.to_string(), "(count(export) <= 0) except [ROLE:auditor, health_checks]");
+    }
+}
@@ -0,0 +1,304 @@
+//! Stateful enforcement of ICAE cost ceilings over budget periods.
+//!
+//! The ledger sums usage records per constraint and per budget window, and raises
+//! an alert when a window's total approaches or exceeds the constraint's ceiling.
+//! Timestamps are Unix seconds; calendar windows (weeks, months, quarters, years)
+//! follow UTC, with weeks starting on Monday.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{BudgetPeriod, ICAECostConstraint, MeasurementUnit};
+
+/// Share of the ceiling at which a window is reported as approaching it.
+const DEFAULT_WARNING_RATIO: f64 = 0.8;
+
+const SECONDS_PER_HOUR: u64 = 3_600;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Usage of `amount` units charged to `subject` at `timestamp` (Unix seconds).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UsageRecord {
+    pub subject: String,
+    pub amount: f64,
+    pub unit: MeasurementUnit,
+    pub timestamp: u64,
+}
+
+/// The half-open interval `start..end` of Unix seconds a total is kept for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BudgetWindow {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl BudgetWindow {
+    /// Returns the window of `period` containing `timestamp`. A constraint without a
+    /// period, and `lifetime`, use a single window covering all time; `per_request`
+    /// windows hold one record each. Windows reaching past `u64::MAX` end there.
+    pub fn containing(period: Option<BudgetPeriod>, timestamp: u64) -> Self {
+        let aligned = |length: u64| {
+            let start = timestamp - timestamp % length;
+            BudgetWindow { start, end: start.saturating_add(length) }
+        };
+        let (year, month, _) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+        let months = |first_month: i64, length: i64| BudgetWindow {
+            start: month_start(year, first_month),
+            end: month_start(year, first_month + length),
+        };
+
+        match period {
+            Some(BudgetPeriod::PerRequest) => BudgetWindow { start: timestamp, end: timestamp.saturating_add(1) },
+            Some(BudgetPeriod::Hourly) => aligned(SECONDS_PER_HOUR),
+            Some(BudgetPeriod::Daily) => aligned(SECONDS_PER_DAY),
+            Some(BudgetPeriod::Weekly) => {
+                // 1970-01-01 was a Thursday, three days after a Monday
+                let days = timestamp / SECONDS_PER_DAY;
+                let monday = (days + 3) / 7 * 7;
+                let start = monday.saturating_sub(3) * SECONDS_PER_DAY;
+                BudgetWindow { start, end: (monday + 4).saturating_mul(SECONDS_PER_DAY) }
+            }
+            Some(BudgetPeriod::Monthly) => months(month, 1),
+            Some(BudgetPeriod::Quarterly) => months((month - 1) / 3 * 3 + 1, 3),
+            Some(BudgetPeriod::Yearly) => months(1, 12),
+            Some(BudgetPeriod::Lifetime) | None => BudgetWindow { start: 0, end: u64::MAX },
+        }
+    }
+
+    /// Returns true if `timestamp` falls within the window.
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.start <= timestamp && timestamp < self.end
+    }
+}
+
+/// How close a window's total is to its ceiling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AlertLevel {
+    Approaching,
+    Exceeded,
+}
+
+/// A window total that reached a new `AlertLevel` for a constraint.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LedgerAlert {
+    pub constraint_id: String,
+    pub failure_signal: String,
+    pub clause_index: usize,
+    pub level: AlertLevel,
+    pub window: BudgetWindow,
+    pub total: f64,
+    pub ceiling: f64,
+}
+
+impl fmt::Display for LedgerAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            AlertLevel::Approaching => "approaching",
+            AlertLevel::Exceeded => "exceeded",
+        };
+        write!(f, "{} {}: {} of ceiling {} {}", self.failure_signal, self.constraint_id, self.total, self.ceiling, level)
+    }
+}
+
+/// Running usage totals for a set of ICAE cost constraints.
+///
+/// A record counts towards every constraint with the same unit and subject (compared
+/// case-insensitively). Each window reports each alert level at most once, except
+/// `per_request` constraints, which check every record on its own.
+#[derive(Debug, Clone)]
+pub struct CostLedger {
+    constraints: Vec<ICAECostConstraint>,
+    warning_ratio: f64,
+    // Keyed by constraint id and window start
+    totals: BTreeMap<(String, u64), f64>,
+    levels: BTreeMap<(String, u64), AlertLevel>,
+}
+
+impl CostLedger {
+    /// Creates an empty ledger for `constraints`, warning at 80% of each ceiling.
+    pub fn new(constraints: &[ICAECostConstraint]) -> Self {
+        CostLedger {
+            constraints: constraints.to_vec(),
+            warning_ratio: DEFAULT_WARNING_RATIO,
+            totals: BTreeMap::new(),
+            levels: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the share of the ceiling, between 0 and 1, at which windows are reported as approaching it.
+    pub fn with_warning_ratio(mut self, ratio: f64) -> Self {
+        self.warning_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Adds a usage record and returns the alerts it raises, in constraint order.
+    pub fn record(&mut self, record: &UsageRecord) -> Vec<LedgerAlert> {
+        let mut alerts = Vec::new();
+
+        for constraint in &self.constraints {
+            if constraint.measurement_unit != record.unit || !constraint.subject.eq_ignore_ascii_case(&record.subject) {
+                continue;
+            }
+            let window = BudgetWindow::containing(constraint.period, record.timestamp);
+            let key = (constraint.id.clone(), window.start);
+            let per_request = constraint.period == Some(BudgetPeriod::PerRequest);
+
+            let total = if per_request {
+                record.amount
+            } else {
+                let total = self.totals.entry(key.clone()).or_insert(0.0);
+                *total += record.amount;
+                *total
+            };
+
+            let ceiling = match constraint.ceiling {
+                Some(ceiling) => ceiling,
+                None => continue,
+            };
+            let level = if total > ceiling {
+                AlertLevel::Exceeded
+            } else if total >= ceiling * self.warning_ratio {
+                AlertLevel::Approaching
+            } else {
+                continue;
+            };
+            if !per_request {
+                if matches!(self.levels.get(&key), Some(reported) if *reported >= level) {
+                    continue;
+                }
+                self.levels.insert(key, level);
+            }
+
+            alerts.push(LedgerAlert {
+                constraint_id: constraint.id.clone(),
+                failure_signal: constraint.failure_signal.clone(),
+                clause_index: constraint.clause_index,
+                level,
+                window,
+                total,
+                ceiling,
+            });
+        }
+
+        alerts
+    }
+
+    /// Returns the usage recorded against `constraint_id` in the window containing `timestamp`.
+    pub fn total(&self, constraint_id: &str, timestamp: u64) -> f64 {
+        self.constraints
+            .iter()
+            .find(|constraint| constraint.id == constraint_id)
+            .map(|constraint| BudgetWindow::containing(constraint.period, timestamp))
+            .and_then(|window| self.totals.get(&(constraint_id.to_string(), window.start)))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Returns how much of the ceiling is left in the window containing `timestamp`,
+    /// or `None` if the constraint is unknown or has no ceiling.
+    pub fn remaining(&self, constraint_id: &str, timestamp: u64) -> Option<f64> {
+        let constraint = self.constraints.iter().find(|constraint| constraint.id == constraint_id)?;
+        Some(constraint.ceiling? - self.total(constraint_id, timestamp))
+    }
+}
+
+/// Returns the Unix timestamp of midnight UTC on the first day of `month` in
+/// `year`. Months past December roll over into later years.
+fn month_start(year: i64, month: i64) -> u64 {
+    let year = year + (month - 1) / 12;
+    let month = (month - 1) % 12 + 1;
+    (days_from_civil(year, month, 1).max(0) as u64).saturating_mul(SECONDS_PER_DAY)
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date in the proleptic
+/// Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a proleptic Gregorian date to days since 1970-01-01.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_date_round_trip() {
+        // 2024-02-29, a leap day
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+    }
+
+    #[test]
+    fn test_calendar_windows() {
+        // 2024-05-15T12:00:00Z, a Wednesday
+        let timestamp = 1_715_774_400;
+        let window = |period| BudgetWindow::containing(Some(period), timestamp);
+
+        assert_eq!(window(BudgetPeriod::Daily), BudgetWindow { start: 1_715_731_200, end: 1_715_817_600 });
+        // Monday 2024-05-13 to Monday 2024-05-20
+        assert_eq!(window(BudgetPeriod::Weekly), BudgetWindow { start: 1_715_558_400, end: 1_716_163_200 });
+        // 2024-05-01 to 2024-06-01
+        assert_eq!(window(BudgetPeriod::Monthly), BudgetWindow { start: 1_714_521_600, end: 1_717_200_000 });
+        // 2024-04-01 to 2024-07-01
+        assert_eq!(window(BudgetPeriod::Quarterly), BudgetWindow { start: 1_711_929_600, end: 1_719_792_000 });
+        // 2024-01-01 to 2025-01-01
+        assert_eq!(window(BudgetPeriod::Yearly), BudgetWindow { start: 1_704_067_200, end: 1_735_689_600 });
+        assert!(BudgetWindow::containing(None, timestamp).contains(timestamp));
+    }
+
+    #[test]
+    fn test_windows_near_the_end_of_time() {
+        for period in [
+            BudgetPeriod::PerRequest,
+            BudgetPeriod::Hourly,
+            BudgetPeriod::Daily,
+            BudgetPeriod::Weekly,
+            BudgetPeriod::Monthly,
+            BudgetPeriod::Quarterly,
+            BudgetPeriod::Yearly,
+        ] {
+            for timestamp in [u64::MAX, u64::MAX - 1, u64::MAX - SECONDS_PER_DAY] {
+                let window = BudgetWindow::containing(Some(period), timestamp);
+                assert!(window.start <= timestamp && window.end >= timestamp, "{:?} at {}", period, timestamp);
+            }
+        }
+        let window = BudgetWindow::containing(Some(BudgetPeriod::PerRequest), u64::MAX);
+        assert_eq!(window, BudgetWindow { start: u64::MAX, end: u64::MAX });
+    }
+
+    #[test]
+    fn test_december_rolls_into_next_year() {
+        // 2023-12-31T23:00:00Z
+        let window = BudgetWindow::containing(Some(BudgetPeriod::Monthly), 1_704_063_600);
+        assert_eq!(window, BudgetWindow { start: 1_701_388_800, end: 1_704_067_200 });
+    }
+}
@@ -1,11 +1,33 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::vec::Vec;
 use std::fmt;
 use std::error::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod dsl;
+mod evaluator;
+mod exclusion;
+mod guard;
+mod invariant;
+mod ledger;
+mod markdown;
+mod segmenter;
+mod tokenizer;
+
+pub use evaluator::{ArtifactKind, AuthorizationEvent, CostEvent, Evaluator, PolicyEvent, Violation};
+pub use exclusion::{Assumption, Exclusion, ExclusionTarget};
+pub use guard::Guard;
+pub use invariant::{EventPattern, ExecutionEvent, InvariantPredicate, InvariantStep, PrecedenceEdge};
+pub use ledger::{AlertLevel, BudgetWindow, CostLedger, LedgerAlert, UsageRecord};
+use tokenizer::{contains_word, find_phrase, find_term, is_inflection_of, tokenize, Token};
  
 /// Compilation status indicating pass or fail verdict.
 /// Uses SCREAMING_CASE variants per Rust enum conventions for C-style enums.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
 pub enum CompilationStatus {
     Pass,
     Fail,
@@ -20,21 +42,220 @@ impl fmt::Display for CompilationStatus {
     }
 }
 
+/// Location of a fragment of policy text within the original `policy_input`.
+///
+/// Byte offsets are half-open (`start..end`). Lines and columns are 1-based,
+/// with columns counted in characters so editors can underline the fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// Returns the length of the span in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the span covers no text.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the text covered by this span in the source it was computed from.
+    pub fn text<'a>(&self, source: &'a str) -> Option<&'a str> {
+        source.get(self.start..self.end)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Line start offsets of a source text, used to resolve byte offsets to line/column.
+#[derive(Debug, Clone)]
+struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        LineIndex { source, line_starts }
+    }
+
+    /// Resolves a byte offset to a 1-based (line, column) pair.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.source[line_start..offset].chars().count() + 1;
+        (line, column)
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        let (line, column) = self.position(start);
+        let (end_line, end_column) = self.position(end);
+        Span { start, end, line, column, end_line, end_column }
+    }
+}
+
+/// A single policy clause together with its location in the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Clause {
+    pub text: String,
+    pub span: Span,
+}
+
+impl Clause {
+    /// Returns the span of `text[start..end]` relative to the original input.
+    pub fn subspan(&self, start: usize, end: usize) -> Span {
+        let index = LineIndex::new(&self.text);
+        let (rel_line, rel_column) = index.position(start);
+        let (rel_end_line, rel_end_column) = index.position(end);
+        let shift = |line: usize, column: usize| {
+            if line == 1 {
+                (self.span.line, self.span.column + column - 1)
+            } else {
+                (self.span.line + line - 1, column)
+            }
+        };
+        let (line, column) = shift(rel_line, rel_column);
+        let (end_line, end_column) = shift(rel_end_line, rel_end_column);
+        Span {
+            start: self.span.start + start,
+            end: self.span.start + end,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// A numbered item of an outlined policy ("1. Access", "1.2 SYSTEM must ...").
+///
+/// An item with subsections is a heading: its text is kept as `heading` and is not
+/// compiled. `parent` is the number of the closest enclosing section, and
+/// `clause_indices` lists the clauses of the item itself, not of its subsections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Section {
+    pub number: String,
+    pub heading: Option<String>,
+    pub parent: Option<String>,
+    pub clause_indices: Vec<usize>,
+    pub span: Span,
+}
+
 /// Error types for compilation failures with structured categorization.
+///
+/// Clause-level variants carry a `span` into the original policy input. For
+/// `ModalLanguageDetected`, `AmbiguousMultiAction`, `AmbiguousPrincipalRole`,
+/// `MissingCeiling` and `AmbiguousBudgetPeriod` (when several periods conflict) it
+/// covers the offending words; for the remaining variants it covers the whole clause.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
 pub enum CompilationError {
     EmptyInput,
     NoClauses,
     IntentNormalizationFailed { reason: String },
-    ModalLanguageDetected { clause_index: usize, clause: String, modal_word: String },
-    MissingActionVerb { clause_index: usize, clause: String },
-    AmbiguousMultiAction { clause_index: usize, clause: String },
-    MissingPrincipal { clause_index: usize, clause: String },
-    MissingMeasurementUnit { clause_index: usize, clause: String },
-    MissingCostSubject { clause_index: usize, clause: String },
+    ModalLanguageDetected { clause_index: usize, clause: String, modal_word: String, span: Span },
+    MissingActionVerb { clause_index: usize, clause: String, span: Span },
+    AmbiguousMultiAction { clause_index: usize, clause: String, span: Span },
+    MissingPrincipal { clause_index: usize, clause: String, span: Span },
+    /// A clause names several principals and the role of `principal` cannot be determined.
+    AmbiguousPrincipalRole { clause_index: usize, clause: String, principal: String, span: Span },
+    MissingMeasurementUnit { clause_index: usize, clause: String, span: Span },
+    MissingCostSubject { clause_index: usize, clause: String, span: Span },
+    MissingCeiling { clause_index: usize, clause: String, span: Span },
+    AmbiguousBudgetPeriod { clause_index: usize, clause: String, span: Span },
+    /// An authority clause names no resource for its principal to act on.
+    MissingResource { clause_index: usize, clause: String, span: Span },
+    /// A delegation clause lacks a delegating principal or a delegated scope.
+    IncompleteDelegation { clause_index: usize, clause: String, span: Span },
+    /// The delegation in clause `clause_index` closes a cycle; `cycle` lists the
+    /// identities along it, starting and ending with the same one.
+    DelegationCycle { clause_index: usize, clause: String, cycle: Vec<String>, span: Span },
+    /// Clauses `a` and `b` (with `a < b`) allow and deny the same subject; `span` covers clause `b`.
+    ConflictingClauses { a: usize, b: usize, span: Span },
+    /// A DSL declaration does not parse, or names an unknown principal; `span`
+    /// covers the offending part of the declaration.
+    InvalidDeclaration { clause_index: usize, clause: String, message: String, span: Span },
+    /// A clause opens with a condition ("When ...") whose end cannot be found: no
+    /// comma follows it and no principal opens the rest of the clause.
+    UnterminatedCondition { clause_index: usize, clause: String, span: Span },
+    /// An outline section repeats the number of an earlier one; `span` covers the
+    /// repeated number.
+    DuplicateSection { number: String, span: Span },
     InternalError { context: String },
 }
 
+impl CompilationError {
+    /// Returns the index of the clause this error refers to, if any.
+    pub fn clause_index(&self) -> Option<usize> {
+        match self {
+            CompilationError::ModalLanguageDetected { clause_index, .. }
+            | CompilationError::MissingActionVerb { clause_index, .. }
+            | CompilationError::AmbiguousMultiAction { clause_index, .. }
+            | CompilationError::MissingPrincipal { clause_index, .. }
+            | CompilationError::AmbiguousPrincipalRole { clause_index, .. }
+            | CompilationError::MissingMeasurementUnit { clause_index, .. }
+            | CompilationError::MissingCostSubject { clause_index, .. }
+            | CompilationError::MissingCeiling { clause_index, .. }
+            | CompilationError::AmbiguousBudgetPeriod { clause_index, .. }
+            | CompilationError::MissingResource { clause_index, .. }
+            | CompilationError::IncompleteDelegation { clause_index, .. }
+            | CompilationError::DelegationCycle { clause_index, .. }
+            | CompilationError::InvalidDeclaration { clause_index, .. }
+            | CompilationError::UnterminatedCondition { clause_index, .. } => Some(*clause_index),
+            CompilationError::ConflictingClauses { b, .. } => Some(*b),
+            CompilationError::EmptyInput
+            | CompilationError::NoClauses
+            | CompilationError::IntentNormalizationFailed { .. }
+            | CompilationError::DuplicateSection { .. }
+            | CompilationError::InternalError { .. } => None,
+        }
+    }
+
+    /// Returns the location in the policy input this error points at, if any.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CompilationError::ModalLanguageDetected { span, .. }
+            | CompilationError::MissingActionVerb { span, .. }
+            | CompilationError::AmbiguousMultiAction { span, .. }
+            | CompilationError::MissingPrincipal { span, .. }
+            | CompilationError::AmbiguousPrincipalRole { span, .. }
+            | CompilationError::MissingMeasurementUnit { span, .. }
+            | CompilationError::MissingCostSubject { span, .. }
+            | CompilationError::MissingCeiling { span, .. }
+            | CompilationError::AmbiguousBudgetPeriod { span, .. }
+            | CompilationError::MissingResource { span, .. }
+            | CompilationError::IncompleteDelegation { span, .. }
+            | CompilationError::DelegationCycle { span, .. }
+            | CompilationError::ConflictingClauses { span, .. }
+            | CompilationError::InvalidDeclaration { span, .. }
+            | CompilationError::UnterminatedCondition { span, .. }
+            | CompilationError::DuplicateSection { span, .. } => Some(*span),
+            CompilationError::EmptyInput
+            | CompilationError::NoClauses
+            | CompilationError::IntentNormalizationFailed { .. }
+            | CompilationError::InternalError { .. } => None,
+        }
+    }
+}
+
 impl fmt::Display for CompilationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -43,23 +264,53 @@ impl fmt::Display for CompilationError {
             CompilationError::IntentNormalizationFailed { reason } => {
                 write!(f, "Intent normalization failed: {}", reason)
             }
-            CompilationError::ModalLanguageDetected { clause_index, clause, modal_word } => {
-                write!(f, "Clause {} contains modal language '{}': '{}'", clause_index, modal_word, clause)
+            CompilationError::ModalLanguageDetected { clause_index, clause, modal_word, span } => {
+                write!(f, "Clause {} at {} contains modal language '{}': '{}'", clause_index, span, modal_word, clause)
+            }
+            CompilationError::MissingActionVerb { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} missing action verb: '{}'", clause_index, span, clause)
+            }
+            CompilationError::AmbiguousMultiAction { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} has ambiguous multi-action without ordering: '{}'", clause_index, span, clause)
+            }
+            CompilationError::MissingPrincipal { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} missing explicit principal: '{}'", clause_index, span, clause)
             }
-            CompilationError::MissingActionVerb { clause_index, clause } => {
-                write!(f, "Clause {} missing action verb: '{}'", clause_index, clause)
+            CompilationError::AmbiguousPrincipalRole { clause_index, clause, principal, span } => {
+                write!(f, "Clause {} at {} names principal '{}' without a determinable role: '{}'", clause_index, span, principal, clause)
             }
-            CompilationError::AmbiguousMultiAction { clause_index, clause } => {
-                write!(f, "Clause {} has ambiguous multi-action without ordering: '{}'", clause_index, clause)
+            CompilationError::MissingMeasurementUnit { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} mentions cost but no explicit measurement unit: '{}'", clause_index, span, clause)
             }
-            CompilationError::MissingPrincipal { clause_index, clause } => {
-                write!(f, "Clause {} missing explicit principal: '{}'", clause_index, clause)
+            CompilationError::MissingCostSubject { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} mentions cost but no attribution subject: '{}'", clause_index, span, clause)
             }
-            CompilationError::MissingMeasurementUnit { clause_index, clause } => {
-                write!(f, "Clause {} mentions cost but no explicit measurement unit: '{}'", clause_index, clause)
+            CompilationError::MissingCeiling { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} sets a cost limit but gives no numeric ceiling: '{}'", clause_index, span, clause)
             }
-            CompilationError::MissingCostSubject { clause_index, clause } => {
-                write!(f, "Clause {} mentions cost but no attribution subject: '{}'", clause_index, clause)
+            CompilationError::AmbiguousBudgetPeriod { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} sets a ceiling without exactly one budget period: '{}'", clause_index, span, clause)
+            }
+            CompilationError::MissingResource { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} grants authority without an identifiable resource: '{}'", clause_index, span, clause)
+            }
+            CompilationError::IncompleteDelegation { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} delegates authority without a delegating principal and scope: '{}'", clause_index, span, clause)
+            }
+            CompilationError::DelegationCycle { clause_index, cycle, span, .. } => {
+                write!(f, "Clause {} at {} closes a delegation cycle: {}", clause_index, span, cycle.join(" -> "))
+            }
+            CompilationError::ConflictingClauses { a, b, span } => {
+                write!(f, "Clause {} at {} contradicts clause {}: both govern the same principal and subject", b, span, a)
+            }
+            CompilationError::InvalidDeclaration { clause_index, message, span, .. } => {
+                write!(f, "Clause {} at {} is not a valid declaration: {}", clause_index, span, message)
+            }
+            CompilationError::UnterminatedCondition { clause_index, clause, span } => {
+                write!(f, "Clause {} at {} opens with a condition that has no end; follow it with a comma: '{}'", clause_index, span, clause)
+            }
+            CompilationError::DuplicateSection { number, span } => {
+                write!(f, "Section {} at {} repeats an earlier section number", number, span)
             }
             CompilationError::InternalError { context } => {
                 write!(f, "Internal error: {}", context)
@@ -72,6 +323,8 @@ impl Error for CompilationError {}
 
 /// Known principals for zero-trust authority validation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
 pub enum Principal {
     System,
     User,
@@ -88,23 +341,21 @@ impl Principal {
         }
     }
 
-    /// Attempts to parse a principal from text using word boundary detection.
+    /// Attempts to parse a built-in principal from text using word boundary detection.
+    /// Use `PrincipalRegistry::resolve` to also recognize named principals.
     pub fn from_clause(clause: &str) -> Option<Self> {
-        let clause_upper = clause.to_uppercase();
-        let tokens: Vec<&str> = clause_upper.split_whitespace()
-            .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()))
-            .collect();
-        
         // Check tokens for exact matches (word boundary detection)
-        for token in &tokens {
-            match *token {
-                "SYSTEM" => return Some(Principal::System),
-                "USER" => return Some(Principal::User),
-                "SERVICE" => return Some(Principal::Service),
-                _ => continue,
-            }
+        tokenize(clause).iter().find_map(|token| Self::from_word(&token.lower))
+    }
+
+    /// Maps a single lowercase word to a principal.
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "system" => Some(Principal::System),
+            "user" => Some(Principal::User),
+            "service" => Some(Principal::Service),
+            _ => None,
         }
-        None
     }
 }
 
@@ -114,15 +365,120 @@ impl fmt::Display for Principal {
     }
 }
 
+/// A specific identity named in a clause, together with its principal kind.
+///
+/// Built-in principals resolve to themselves (identity `"SYSTEM"`, `"USER"` or
+/// `"SERVICE"`); registered principals keep the name they were declared with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NamedPrincipal {
+    pub identity: String,
+    pub kind: Principal,
+}
+
+/// Deployment-specific principals such as `billing-service`, `ROLE:auditor` or
+/// `svc:ingest`, each declared with a kind: `Principal::User` for humans,
+/// `Principal::Service` for services and `Principal::System` for the platform.
+///
+/// Names are matched case-insensitively on word boundaries and may span several
+/// words. The built-in SYSTEM, USER and SERVICE principals are always known.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrincipalRegistry {
+    // Keyed by lowercase name for deterministic, case-insensitive lookup
+    entries: BTreeMap<String, NamedPrincipal>,
+}
+
+impl PrincipalRegistry {
+    /// Creates a registry containing only the built-in principals.
+    pub fn new() -> Self {
+        PrincipalRegistry::default()
+    }
+
+    /// Declares a named principal, replacing any earlier declaration of the same name.
+    /// Blank names are ignored.
+    pub fn register(&mut self, name: &str, kind: Principal) {
+        let identity = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !identity.is_empty() {
+            self.entries.insert(identity.to_lowercase(), NamedPrincipal { identity, kind });
+        }
+    }
+
+    /// Builder-style variant of `register`.
+    pub fn with(mut self, name: &str, kind: Principal) -> Self {
+        self.register(name, kind);
+        self
+    }
+
+    /// Looks up a declared principal by name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&NamedPrincipal> {
+        let key = name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        self.entries.get(&key)
+    }
+
+    /// Returns the declared principals in name order.
+    pub fn iter(&self) -> impl Iterator<Item = &NamedPrincipal> {
+        self.entries.values()
+    }
+
+    /// Resolves the first principal mentioned in `clause`, built-in or registered.
+    pub fn resolve(&self, clause: &str) -> Option<NamedPrincipal> {
+        self.mentions(clause).into_iter().next().map(|(_, principal)| principal)
+    }
+
+    /// Resolves every principal mentioned in `clause`, in text order.
+    pub fn resolve_all(&self, clause: &str) -> Vec<NamedPrincipal> {
+        self.mentions(clause).into_iter().map(|(_, principal)| principal).collect()
+    }
+
+    /// Finds every principal mention in `clause` in text order with its byte range.
+    /// Registered names take precedence over built-in words they overlap.
+    fn mentions(&self, clause: &str) -> Vec<((usize, usize), NamedPrincipal)> {
+        let tokens = tokenize(clause);
+        let mut found: Vec<((usize, usize), NamedPrincipal)> = Vec::new();
+
+        for entry in self.entries.values() {
+            let parts = entry.identity.split_whitespace().count();
+            for window in tokens.windows(parts) {
+                if let Some(range) = find_phrase(window, &entry.identity) {
+                    found.push((range, entry.clone()));
+                }
+            }
+        }
+
+        for token in &tokens {
+            let overlaps = found.iter().any(|((start, end), _)| token.offset < *end && token.end() > *start);
+            if overlaps {
+                continue;
+            }
+            if let Some(kind) = Principal::from_word(&token.lower) {
+                let identity = kind.as_str().to_string();
+                found.push(((token.offset, token.end()), NamedPrincipal { identity, kind }));
+            }
+        }
+
+        found.sort_by_key(|(range, _)| *range);
+        found
+    }
+}
+
 /// Known measurement units for ICAE cost constraints.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MeasurementUnit {
+    #[cfg_attr(feature = "serde", serde(rename = "USD"))]
     Usd,
+    #[cfg_attr(feature = "serde", serde(rename = "EUR"))]
     Eur,
+    #[cfg_attr(feature = "serde", serde(rename = "GBP"))]
     Gbp,
+    #[cfg_attr(feature = "serde", serde(rename = "tokens"))]
     Tokens,
+    #[cfg_attr(feature = "serde", serde(rename = "bytes"))]
     Bytes,
+    #[cfg_attr(feature = "serde", serde(rename = "requests"))]
     Requests,
+    #[cfg_attr(feature = "serde", serde(rename = "hours"))]
     Hours,
 }
 
@@ -140,25 +496,27 @@ impl MeasurementUnit {
         }
     }
 
-    /// Attempts to parse a measurement unit from text.
+    /// All units in detection priority order.
+    const ALL: [MeasurementUnit; 7] = [
+        MeasurementUnit::Usd,
+        MeasurementUnit::Eur,
+        MeasurementUnit::Gbp,
+        MeasurementUnit::Tokens,
+        MeasurementUnit::Bytes,
+        MeasurementUnit::Requests,
+        MeasurementUnit::Hours,
+    ];
+
+    /// Attempts to parse a measurement unit from text using word boundary detection.
     pub fn from_clause(clause: &str) -> Option<Self> {
-        let clause_lower = clause.to_lowercase();
-        
         // Check for currency symbols first - these are NOT valid units
         if ["$", "€", "£", "¥", "â‚¬", "Â£", "Â¥"].iter().any(|s| clause.contains(s)) {
             return None;
         }
         
-        // Check for explicit unit names (case-insensitive)
-        if clause_lower.contains("usd") { return Some(MeasurementUnit::Usd); }
-        if clause_lower.contains("eur") { return Some(MeasurementUnit::Eur); }
-        if clause_lower.contains("gbp") { return Some(MeasurementUnit::Gbp); }
-        if clause_lower.contains("tokens") { return Some(MeasurementUnit::Tokens); }
-        if clause_lower.contains("bytes") { return Some(MeasurementUnit::Bytes); }
-        if clause_lower.contains("requests") { return Some(MeasurementUnit::Requests); }
-        if clause_lower.contains("hours") { return Some(MeasurementUnit::Hours); }
-        
-        None
+        // Check for explicit unit names (case-insensitive, whole words only)
+        let tokens = tokenize(clause);
+        Self::ALL.iter().copied().find(|unit| contains_word(&tokens, &[unit.as_str().to_lowercase().as_str()]))
     }
 }
 
@@ -168,141 +526,1032 @@ impl fmt::Display for MeasurementUnit {
     }
 }
 
+/// Time window over which an ICAE ceiling applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BudgetPeriod {
+    PerRequest,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+    Lifetime,
+}
+
+impl BudgetPeriod {
+    /// Returns the canonical string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::PerRequest => "per_request",
+            BudgetPeriod::Hourly => "hourly",
+            BudgetPeriod::Daily => "daily",
+            BudgetPeriod::Weekly => "weekly",
+            BudgetPeriod::Monthly => "monthly",
+            BudgetPeriod::Quarterly => "quarterly",
+            BudgetPeriod::Yearly => "yearly",
+            BudgetPeriod::Lifetime => "lifetime",
+        }
+    }
+
+    /// Maps a single period word ("month", "monthly", "annually") to a period.
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "request" | "call" => Some(BudgetPeriod::PerRequest),
+            "hour" | "hourly" => Some(BudgetPeriod::Hourly),
+            "day" | "daily" => Some(BudgetPeriod::Daily),
+            "week" | "weekly" => Some(BudgetPeriod::Weekly),
+            "month" | "monthly" => Some(BudgetPeriod::Monthly),
+            "quarter" | "quarterly" => Some(BudgetPeriod::Quarterly),
+            "year" | "yearly" | "annually" | "annum" => Some(BudgetPeriod::Yearly),
+            "lifetime" => Some(BudgetPeriod::Lifetime),
+            _ => None,
+        }
+    }
+
+    /// Finds every period mentioned in a clause together with its byte range.
+    ///
+    /// Unit nouns count only after "per", "each", "every", "a"/"an" or a slash
+    /// ("per month", "USD/month"); adverbs such as "monthly" and the phrases
+    /// "lifetime", "in total" and "all time" count on their own.
+    fn find_in_clause(clause: &str) -> Vec<(Self, (usize, usize))> {
+        let tokens = tokenize(clause);
+        let mut found = Vec::new();
+
+        for (k, token) in tokens.iter().enumerate() {
+            let previous = k.checked_sub(1).map(|p| tokens[p].lower.as_str());
+            let after_slash = clause[..token.offset].ends_with('/');
+
+            let period = match token.lower.as_str() {
+                "hourly" | "daily" | "weekly" | "monthly" | "quarterly" | "yearly" | "annually" | "lifetime" => {
+                    Self::from_word(&token.lower)
+                }
+                "total" if previous == Some("in") => Some(BudgetPeriod::Lifetime),
+                "time" if previous == Some("all") => Some(BudgetPeriod::Lifetime),
+                noun => {
+                    let introduced = after_slash || matches!(
+                        previous,
+                        Some("per") | Some("each") | Some("every") | Some("a") | Some("an")
+                    );
+                    if introduced { Self::from_word(noun) } else { None }
+                }
+            };
+
+            if let Some(period) = period {
+                found.push((period, (token.offset, token.end())));
+            }
+        }
+
+        found
+    }
+}
+
+impl fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Modal words that indicate non-deterministic policy language.
 const MODAL_WORDS: &[&str] = &["should", "may", "where reasonable", "as appropriate", "could", "might", "possibly"];
 
 /// Action verbs required for valid policy clauses.
-const ACTION_VERBS: &[&str] = &["must", "shall", "require", "log", "audit", "record", "deny", "allow", "enforce", "track", "exceed"];
+const ACTION_VERBS: &[&str] = &["must", "shall", "require", "log", "audit", "record", "deny", "allow", "enforce", "track", "exceed", "delegate", "revoke"];
 
 /// Cost indicator terms that trigger ICAE constraint validation.
 const COST_INDICATORS: &[&str] = &["cost", "spend", "usage", "quota", "resource consumption", "externality", "budget", "expense"];
 
+/// Limit terms that require a cost clause to state a numeric ceiling.
+const LIMIT_INDICATORS: &[&str] = &["exceed", "limit", "cap", "ceiling", "maximum", "max", "at most", "up to"];
+
+/// Verbs that grant access in authority clauses.
+const ALLOW_VERBS: &[&str] = &["allow", "permit", "grant"];
+
+/// Verbs that refuse access in authority clauses.
+const DENY_VERBS: &[&str] = &["deny", "block", "forbid", "reject"];
+
+/// Verbs whose subject is an approver.
+const APPROVER_VERBS: &[&str] = &["approve", "authorize", "authorise", "ratify", "endorse"];
+
+/// Verbs whose subject is an auditor.
+const AUDITOR_VERBS: &[&str] = &["audit", "review", "inspect", "monitor", "oversee"];
+
+/// Verbs that hand authority to the principal named after "to".
+const DELEGATE_VERBS: &[&str] = &["delegate", "assign", "transfer"];
+
+/// Words that may sit between a principal and its verb.
+const AUXILIARY_WORDS: &[&str] = &["must", "shall", "will", "can", "cannot", "is", "are", "be", "then", "also", "only", "not", "never"];
+
+/// Words that negate a clause's predicate; contractions ending in "n't" also count.
+const NEGATION_WORDS: &[&str] = &["not", "never", "cannot"];
+
+/// Modals granting or withholding permission; followed by a verb they count as the
+/// clause's action ("USER can read reports").
+const PERMISSION_MODALS: &[&str] = &["can", "cannot"];
+
+/// Words that introduce the predicate of a clause ("Reports must be ...", "Backups are ...").
+const PREDICATE_MARKERS: &[&str] = &["must", "shall", "will", "can", "cannot", "is", "are"];
+
+/// Words that separate the steps of an ordered clause.
+const STEP_MARKERS: &[&str] = &["then", "before", "after"];
+
+/// Words that refer back to the previous step's resource ("log actions then audit them").
+const STEP_PRONOUNS: &[&str] = &["it", "them", "this", "these", "those"];
+
+/// Verbs whose object is itself an action on a resource ("allow export of data").
+const ACCESS_VERBS: &[&str] = &["allow", "permit", "grant", "deny", "block", "forbid", "reject", "revoke"];
+
+/// Words skipped before an action or resource name.
+const SCOPE_FILLER: &[&str] = &["the", "a", "an", "all", "any", "every", "each", "no", "its", "their", "of", "to", "for", "on"];
+
+/// Words that end a resource name.
+const SCOPE_BOUNDARIES: &[&str] = &[
+    "by", "after", "before", "then", "when", "on", "for", "with", "unless", "except", "within", "until",
+    "if", "to", "of", "from", "in", "at", "and", "or", "must", "shall", "will", "is", "are", "be", "per",
+    "once", "upon", "via", "without", "not", "the", "a", "an", "including", "containing", "excluding",
+];
+
+/// Words naming authority in general rather than a resource: "revoke USER access".
+const AUTHORITY_WORDS: &[&str] = &["access", "authority", "permission", "permissions", "privileges", "rights"];
+
+/// Parses a numeric quantity such as `1000`, `1,000.50`, `99.5`, `1k` or `2.5M`.
+///
+/// Thousands separators must group exactly three digits. The suffixes `k`, `m`
+/// and `b` (case-insensitive) scale by a thousand, million and billion.
+fn parse_quantity(text: &str) -> Option<f64> {
+    let (number, multiplier) = match text.chars().last()?.to_ascii_lowercase() {
+        'k' => (&text[..text.len() - 1], 1_000.0),
+        'm' => (&text[..text.len() - 1], 1_000_000.0),
+        'b' => (&text[..text.len() - 1], 1_000_000_000.0),
+        _ => (text, 1.0),
+    };
+
+    let (integer, fraction) = match number.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (number, None),
+    };
+    if integer.is_empty() {
+        return None;
+    }
+
+    let groups: Vec<&str> = integer.split(',').collect();
+    let valid_groups = groups.iter().enumerate().all(|(k, group)| {
+        let digits = !group.is_empty() && group.chars().all(|c| c.is_ascii_digit());
+        match k {
+            0 => digits && (groups.len() == 1 || group.len() <= 3),
+            _ => digits && group.len() == 3,
+        }
+    });
+    if !valid_groups {
+        return None;
+    }
+    if let Some(frac) = fraction {
+        if frac.is_empty() || !frac.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+    }
+
+    let digits: String = integer.chars().filter(|c| *c != ',').collect();
+    let value: f64 = match fraction {
+        Some(frac) => format!("{}.{}", digits, frac).parse().ok()?,
+        None => digits.parse().ok()?,
+    };
+    Some(value * multiplier)
+}
+
+/// DSL declarations of a policy, keyed by the index of the clause they occupy.
+type Declarations = BTreeMap<usize, Result<dsl::Declaration, dsl::DslError>>;
+
+/// What a revocation clause withdraws: the granted authorities of `identities`
+/// (of every principal when empty), on `resource` when one is named, once any of
+/// `triggers` occurs.
+struct Revocation {
+    identities: Vec<String>,
+    resource: Option<String>,
+    triggers: Vec<RevocationTrigger>,
+}
+
+/// Returns the byte ranges of the steps of an ordered clause, with the marker
+/// introducing each ("then", "before", "after"; `None` for the first step). Each
+/// step starts after a marker not followed by a quantity or filler ("after 30
+/// days", "before the deadline") and ends at the next step's marker. A clause
+/// without markers is a single step.
+fn step_ranges<'t>(clause: &str, tokens: &'t [Token<'_>]) -> Vec<(usize, usize, Option<&'t str>)> {
+    let mut ranges = vec![(0, clause.len(), None)];
+    for (k, token) in tokens.iter().enumerate().skip(1) {
+        match tokens.get(k + 1) {
+            Some(next) if STEP_MARKERS.contains(&token.lower.as_str())
+                && parse_quantity(next.text).is_none()
+                && !SCOPE_FILLER.contains(&next.lower.as_str()) =>
+            {
+                if let Some(previous) = ranges.last_mut() {
+                    previous.1 = token.offset;
+                }
+                ranges.push((next.offset, clause.len(), Some(token.lower.as_str())));
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
+/// Returns the clauses written in natural language, skipping declarations.
+fn text_clauses<'a>(clauses: &'a [Clause], declarations: &'a Declarations) -> impl Iterator<Item = (usize, &'a Clause)> {
+    clauses.iter().enumerate().filter(move |(i, _)| !declarations.contains_key(i))
+}
+
+/// Normalized clauses with the polarity of each, in clause order, the outline
+/// sections that number them, and the assumptions and exclusions they state.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IntentNormalization {
-    pub clauses: Vec<String>,
-    pub assumptions: Vec<String>,
-    pub exclusions: Vec<String>,
+    pub clauses: Vec<Clause>,
+    pub sections: Vec<Section>,
+    pub polarities: Vec<Polarity>,
+    pub assumptions: Vec<Assumption>,
+    pub exclusions: Vec<Exclusion>,
 }
 
+/// An execution constraint compiled from one clause. `predicate` is checked against
+/// execution traces; `clause_text` keeps the full source clause for provenance.
+///
+/// Ordered multi-step clauses ("A then B", "A before B", "B after A") list their
+/// `steps` in text order and the `precedence` edges between them; both are empty
+/// for single-step clauses. `guard` holds the clause's condition, if any.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DIOInvariant {
     pub id: String,
     pub description: String,
     pub clause_index: usize,
+    pub clause_text: String,
+    pub steps: Vec<InvariantStep>,
+    pub precedence: Vec<PrecedenceEdge>,
+    pub polarity: Polarity,
+    pub predicate: InvariantPredicate,
+    pub guard: Option<Guard>,
     pub failure_signal: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct ZTAuthority {
-    pub id: String,
-    pub principal: Principal,
-    pub scope: String,
-    pub clause_index: usize,
-    pub delegation_rules: Vec<String>,
-    pub revocation_triggers: Vec<String>,
+/// Access decision an authority clause grants or refuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AccessEffect {
+    Allow,
+    Deny,
 }
 
-#[derive(Debug, Clone)]
-pub struct ICAECostConstraint {
-    pub id: String,
-    pub subject: String,
-    pub measurement_unit: MeasurementUnit,
-    pub clause_index: usize,
-    pub ceiling: Option<f64>,
-    pub externalities: Vec<String>,
+impl AccessEffect {
+    /// Detects the access decision stated by a clause, if it states exactly one.
+    pub fn from_clause(clause: &str) -> Option<Self> {
+        let tokens = tokenize(clause);
+        let allows = ALLOW_VERBS.iter().any(|verb| find_term(&tokens, verb).is_some());
+        let denies = DENY_VERBS.iter().any(|verb| find_term(&tokens, verb).is_some());
+        match (allows, denies) {
+            (true, false) => Some(AccessEffect::Allow),
+            (false, true) => Some(AccessEffect::Deny),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct TraceabilityEntry {
-    pub clause_id: String,
-    pub clause_index: usize,
-    pub clause_text: String,
-    pub invariant_ids: Vec<String>,
-    pub authority_ids: Vec<String>,
-    pub cost_ids: Vec<String>,
+/// Whether a clause requires, forbids or merely permits its action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Polarity {
+    /// "SYSTEM must log access"
+    Obligation,
+    /// "SYSTEM must not log access", "Deny USER access to payroll"
+    Prohibition,
+    /// "USER can read reports", "Allow USER access to payroll"
+    Permission,
 }
 
-#[derive(Debug, Clone)]
-pub struct CompilationResult {
-    pub intent_normalization: IntentNormalization,
-    pub dio_invariants: Vec<DIOInvariant>,
-    pub zt_authority_graph: Vec<ZTAuthority>,
-    pub icae_constraints: Vec<ICAECostConstraint>,
-    pub traceability_map: Vec<TraceabilityEntry>,
-    pub verdict: CompilationStatus,
-    pub errors: Vec<CompilationError>,
-    /// Legacy field for backward compatibility - use errors instead
-    #[deprecated(note = "Use errors field instead for structured error handling")]
-    pub failures: Vec<String>,
-}
+impl Polarity {
+    /// Detects the polarity of a clause, ignoring its condition phrase. Negation
+    /// ("must not", "cannot", "never", "don't", "no" before an allowing verb) or a
+    /// denying verb makes a prohibition; an allowing verb or "can" makes a
+    /// permission; anything else is an obligation.
+    pub fn from_clause(clause: &str) -> Self {
+        let condition = guard::find_guard(clause, &PrincipalRegistry::new()).map(|(_, range)| range);
+        let tokens: Vec<Token<'_>> = tokenize(clause)
+            .into_iter()
+            .filter(|t| !condition.iter().any(|(start, end)| (*start..*end).contains(&t.offset)))
+            .collect();
 
-impl CompilationResult {
-    /// Returns true if compilation succeeded.
-    pub fn is_success(&self) -> bool {
-        self.verdict == CompilationStatus::Pass
+        if tokens.iter().any(|t| NEGATION_WORDS.contains(&t.lower.as_str()) || t.lower.ends_with("n't")) {
+            return Polarity::Prohibition;
+        }
+        let allowed_at = ALLOW_VERBS.iter().filter_map(|verb| find_term(&tokens, verb)).map(|(start, _)| start).min();
+        // "No unauthorized access allowed by USER"
+        if matches!(allowed_at, Some(at) if tokens.iter().any(|t| t.lower == "no" && t.offset < at)) {
+            return Polarity::Prohibition;
+        }
+        let allows = allowed_at.is_some();
+        let denies = DENY_VERBS.iter().any(|verb| find_term(&tokens, verb).is_some());
+        match (allows, denies) {
+            (false, true) => Polarity::Prohibition,
+            (true, false) => Polarity::Permission,
+            _ if contains_word(&tokens, &["can"]) => Polarity::Permission,
+            _ => Polarity::Obligation,
+        }
     }
 
-    /// Returns formatted error messages.
-    pub fn error_messages(&self) -> Vec<String> {
-        self.errors.iter().map(|e| e.to_string()).collect()
+    /// Returns true if clauses of these polarities cannot both hold for the same
+    /// action: a prohibition contradicts an obligation or a permission.
+    pub fn contradicts(self, other: Polarity) -> bool {
+        (self == Polarity::Prohibition) != (other == Polarity::Prohibition)
     }
 }
 
-/// Policy compiler with deterministic output guarantees.
-/// 
-/// # Determinism
-/// Given identical input, the compiler produces identical output. All internal
-/// collections use deterministic ordering (BTreeMap over HashMap for iteration).
-#[derive(Debug, Clone, Default)]
-pub struct PolicyCompiler {
-    _private: (),  // Prevents direct construction, enforces ::new()
+/// The action an authority clause governs and the resource it acts on, written
+/// `action:resource` (e.g. `read:customer_records`, `export:reports`).
+///
+/// The action is the clause's verb, in base form when it is a known verb. For
+/// access verbs ("allow export of data") it is the action being allowed or denied, when one is named.
+/// The resource is the object of the verb, or the subject of a passive clause,
+/// lowercased with words joined by underscores.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuthorityScope {
+    pub action: String,
+    pub resource: String,
 }
 
-impl PolicyCompiler {
-    /// Creates a new PolicyCompiler instance.
-    pub fn new() -> Self {
-        PolicyCompiler {
-            _private: (),
-        }
+impl fmt::Display for AuthorityScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.action, self.resource)
     }
+}
 
-    /// Compiles a policy string into governance artifacts.
-    /// 
-    /// # Arguments
-    /// * `policy_input` - The policy text to compile
-    /// 
-    /// # Returns
-    /// A CompilationResult containing all artifacts or error details.
-    /// 
-    /// # Thread Safety
-    /// This method is stateless and inherently thread-safe.
-    /// Multiple threads can call compile() concurrently without synchronization.
-    pub fn compile(&self, policy_input: &str) -> CompilationResult {
-        let policy_text = policy_input.trim().to_string();
-        if policy_text.is_empty() {
-            return Self::fail_with_error(CompilationError::EmptyInput);
-        }
-        // Local state for assumptions and exclusions
-        let mut assumptions = Vec::new();
-        let mut exclusions = Vec::new();
-
+/// Part a principal plays in the clause that names it.
+///
+/// A principal followed by its verb ("SYSTEM must approve") or named as the agent
+/// of a passive ("approved by SYSTEM") takes its role from that verb. A principal
+/// receiving delegated authority ("delegate access to SERVICE") is a delegate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AuthorityRole {
+    Actor,
+    Approver,
+    Delegate,
+    Auditor,
+}
 
-        // Parse clauses
-        let clauses = Self::parse_clauses(&policy_text);
-        if clauses.is_empty() {
-            return Self::fail_with_error(CompilationError::NoClauses);
+impl AuthorityRole {
+    /// Returns the canonical string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthorityRole::Actor => "actor",
+            AuthorityRole::Approver => "approver",
+            AuthorityRole::Delegate => "delegate",
+            AuthorityRole::Auditor => "auditor",
         }
+    }
+
+    /// Maps a verb to the role of the principal performing it. Verbs without a
+    /// dedicated role make the principal an actor.
+    fn from_verb(word: &str) -> Self {
+        let is = |verbs: &[&str]| verbs.iter().any(|verb| is_inflection_of(word, verb));
+        if is(APPROVER_VERBS) {
+            AuthorityRole::Approver
+        } else if is(AUDITOR_VERBS) {
+            AuthorityRole::Auditor
+        } else {
+            AuthorityRole::Actor
+        }
+    }
+}
+
+impl fmt::Display for AuthorityRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Calendar unit of a `TimeSpan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimeUnit {
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl TimeUnit {
+    /// Returns the canonical string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeUnit::Minutes => "minutes",
+            TimeUnit::Hours => "hours",
+            TimeUnit::Days => "days",
+            TimeUnit::Weeks => "weeks",
+            TimeUnit::Months => "months",
+            TimeUnit::Years => "years",
+        }
+    }
+
+    /// Maps a lowercase unit word, singular or plural, to a time unit.
+    fn from_word(word: &str) -> Option<Self> {
+        match word.strip_suffix('s').unwrap_or(word) {
+            "minute" => Some(TimeUnit::Minutes),
+            "hour" => Some(TimeUnit::Hours),
+            "day" => Some(TimeUnit::Days),
+            "week" => Some(TimeUnit::Weeks),
+            "month" => Some(TimeUnit::Months),
+            "year" => Some(TimeUnit::Years),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A whole number of calendar units, such as "30 days".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimeSpan {
+    pub amount: u64,
+    pub unit: TimeUnit,
+}
+
+impl TimeSpan {
+    /// Finds the first "<number> <unit>" in `tokens`, e.g. "30 days" or "1 year".
+    fn find_in_tokens(tokens: &[Token<'_>]) -> Option<Self> {
+        tokens.windows(2).find_map(|pair| {
+            let amount = parse_quantity(pair[0].text).filter(|n| n.fract() == 0.0 && *n >= 0.0)?;
+            let unit = TimeUnit::from_word(&pair[1].lower)?;
+            Some(TimeSpan { amount: amount as u64, unit })
+        })
+    }
+
+    /// Returns the span in minutes, counting months as 30 days and years as 365.
+    pub fn as_minutes(&self) -> u64 {
+        let minutes = match self.unit {
+            TimeUnit::Minutes => 1,
+            TimeUnit::Hours => 60,
+            TimeUnit::Days => 24 * 60,
+            TimeUnit::Weeks => 7 * 24 * 60,
+            TimeUnit::Months => 30 * 24 * 60,
+            TimeUnit::Years => 365 * 24 * 60,
+        };
+        self.amount.saturating_mul(minutes)
+    }
+}
+
+impl fmt::Display for TimeSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.unit)
+    }
+}
+
+/// A grant of authority from one principal to another, compiled from clauses such
+/// as "SYSTEM delegates audit authority to SERVICE with max depth 1 for 30 days".
+///
+/// `max_depth` limits how many further hops the delegate may pass the authority on
+/// (`None` when the clause sets no limit); `expiry` is how long the grant lasts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DelegationEdge {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+    pub scope: String,
+    pub max_depth: Option<u32>,
+    pub expiry: Option<TimeSpan>,
+    pub clause_index: usize,
+}
+
+/// Delegation edges between principal identities, with an adjacency list keyed by
+/// the delegating identity. Both are kept in deterministic order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DelegationGraph {
+    pub edges: Vec<DelegationEdge>,
+    pub adjacency: BTreeMap<String, Vec<String>>,
+}
+
+impl DelegationGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        DelegationGraph::default()
+    }
+
+    /// Adds an edge and records `to` as a delegate of `from`.
+    pub fn add_edge(&mut self, edge: DelegationEdge) {
+        let targets = self.adjacency.entry(edge.from.clone()).or_default();
+        if !targets.contains(&edge.to) {
+            targets.push(edge.to.clone());
+            targets.sort();
+        }
+        self.edges.push(edge);
+    }
+
+    /// Returns the identities `identity` delegates authority to directly.
+    pub fn delegates_of(&self, identity: &str) -> &[String] {
+        self.adjacency.get(identity).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns the shortest delegation chain from `from` to `to`, including both
+    /// ends, or `None` if `to` is not reachable.
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        // Breadth-first search; `previous` records how each identity was reached
+        let mut previous: BTreeMap<&str, &str> = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            for next in self.delegates_of(current) {
+                if next == to {
+                    let mut chain = vec![to.to_string(), current.to_string()];
+                    let mut node = current;
+                    while node != from {
+                        node = previous[node];
+                        chain.push(node.to_string());
+                    }
+                    chain.reverse();
+                    return Some(chain);
+                }
+                if next != from && !previous.contains_key(next.as_str()) {
+                    previous.insert(next, current);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Condition under which an authority is revoked.
+///
+/// Every authority is revoked when its policy changes. Clauses that mention
+/// revocation add further triggers: "revoke USER access after 30 days",
+/// "revoke on role change", "revoke when budget exceeded". A revocation clause
+/// adds them to the authorities it revokes; a grant that mentions its own
+/// revocation keeps them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum RevocationTrigger {
+    PolicyChange,
+    After { duration: TimeSpan },
+    RoleChange,
+    BudgetExceeded,
+}
+
+impl RevocationTrigger {
+    /// Parses the revocation conditions a clause states, in a fixed order. Returns an
+    /// empty list when the clause does not mention revocation. `PolicyChange` is
+    /// implicit and never returned.
+    pub fn from_clause(clause: &str) -> Vec<Self> {
+        let tokens = tokenize(clause);
+        let mut triggers = Vec::new();
+        let mentions_revocation = tokens.iter()
+            .any(|t| is_inflection_of(&t.lower, "revoke") || t.lower == "revocation");
+        if !mentions_revocation {
+            return triggers;
+        }
+
+        let after = tokens.iter().position(|t| t.lower == "after");
+        if let Some(duration) = after.and_then(|k| TimeSpan::find_in_tokens(&tokens[k + 1..(k + 3).min(tokens.len())])) {
+            triggers.push(RevocationTrigger::After { duration });
+        }
+        if find_term(&tokens, "role change").is_some() {
+            triggers.push(RevocationTrigger::RoleChange);
+        }
+        if contains_word(&tokens, &["budget"]) && find_term(&tokens, "exceed").is_some() {
+            triggers.push(RevocationTrigger::BudgetExceeded);
+        }
+        triggers
+    }
+
+    /// Returns whether `tokens` open with a trigger condition: "role change",
+    /// "<number> <time unit>" or "budget ...".
+    fn opens(tokens: &[Token<'_>]) -> bool {
+        match tokens.first() {
+            Some(first) => {
+                first.lower == "budget"
+                    || matches!(find_term(tokens, "role change"), Some((start, _)) if start == first.offset)
+                    || TimeSpan::find_in_tokens(&tokens[..tokens.len().min(2)]).is_some()
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the index of the marker ("on", "after", "when", "upon") opening the
+    /// first trigger phrase in `tokens`.
+    fn phrase_start(tokens: &[Token<'_>]) -> Option<usize> {
+        (1..tokens.len())
+            .find(|k| ["on", "after", "when", "upon"].contains(&tokens[k - 1].lower.as_str()) && Self::opens(&tokens[*k..]))
+            .map(|k| k - 1)
+    }
+}
+
+impl fmt::Display for RevocationTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevocationTrigger::PolicyChange => write!(f, "policy change"),
+            RevocationTrigger::After { duration } => write!(f, "after {}", duration),
+            RevocationTrigger::RoleChange => write!(f, "role change"),
+            RevocationTrigger::BudgetExceeded => write!(f, "budget exceeded"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZTAuthority {
+    pub id: String,
+    pub principal: Principal,
+    pub identity: String,
+    pub role: AuthorityRole,
+    pub scope: AuthorityScope,
+    pub effect: Option<AccessEffect>,
+    pub polarity: Polarity,
+    pub clause_index: usize,
+    pub delegation_rules: Vec<DelegationEdge>,
+    pub revocation_triggers: Vec<RevocationTrigger>,
+    pub guard: Option<Guard>,
+    pub failure_signal: String,
+}
+
+impl ZTAuthority {
+    /// Returns whether `occurred` ends this authority: it is one of its triggers, or
+    /// an elapsed time at least as long as its `After` trigger.
+    pub fn is_revoked_by(&self, occurred: &RevocationTrigger) -> bool {
+        self.revocation_triggers.iter().any(|trigger| match (trigger, occurred) {
+            (RevocationTrigger::After { duration }, RevocationTrigger::After { duration: elapsed }) => {
+                elapsed.as_minutes() >= duration.as_minutes()
+            }
+            _ => trigger == occurred,
+        })
+    }
+}
+
+/// A revocation clause that revokes no authority compiled from the policy. It
+/// revokes the authorities of `identities` (of every principal when empty), on
+/// `resource` when one is named, once any of `triggers` occurs.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RevocationRule {
+    pub id: String,
+    pub identities: Vec<String>,
+    pub resource: Option<String>,
+    pub triggers: Vec<RevocationTrigger>,
+    pub clause_index: usize,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ICAECostConstraint {
+    pub id: String,
+    pub subject: String,
+    pub measurement_unit: MeasurementUnit,
+    pub polarity: Polarity,
+    pub clause_index: usize,
+    pub ceiling: Option<f64>,
+    pub period: Option<BudgetPeriod>,
+    pub externalities: Vec<String>,
+    pub guard: Option<Guard>,
+    pub failure_signal: String,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TraceabilityEntry {
+    pub clause_id: String,
+    pub clause_index: usize,
+    pub clause_text: String,
+    pub span: Span,
+    pub invariant_ids: Vec<String>,
+    pub authority_ids: Vec<String>,
+    pub cost_ids: Vec<String>,
+    pub revocation_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompilationResult {
+    pub intent_normalization: IntentNormalization,
+    pub dio_invariants: Vec<DIOInvariant>,
+    pub zt_authority_graph: Vec<ZTAuthority>,
+    pub delegation_graph: DelegationGraph,
+    pub icae_constraints: Vec<ICAECostConstraint>,
+    pub revocation_rules: Vec<RevocationRule>,
+    pub traceability_map: Vec<TraceabilityEntry>,
+    pub verdict: CompilationStatus,
+    pub errors: Vec<CompilationError>,
+    /// Legacy field for backward compatibility - use errors instead
+    #[deprecated(note = "Use errors field instead for structured error handling")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub failures: Vec<String>,
+}
+
+impl CompilationResult {
+    /// Returns true if compilation succeeded.
+    pub fn is_success(&self) -> bool {
+        self.verdict == CompilationStatus::Pass
+    }
+
+    /// Returns formatted error messages.
+    pub fn error_messages(&self) -> Vec<String> {
+        self.errors.iter().map(|e| e.to_string()).collect()
+    }
+}
+
+/// Controls how many diagnostics a compilation reports before giving up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DiagnosticMode {
+    /// Stop at the first failing validation pass (the default).
+    #[default]
+    FailFast,
+    /// Run every validation pass over every clause and report all errors in clause order.
+    ReportAll,
+}
+
+/// Word lists the compiler uses to validate and classify clauses.
+///
+/// Entries are stored lowercase and may be multi-word phrases. The default
+/// vocabulary is the built-in `MODAL_WORDS`, `ACTION_VERBS` and `COST_INDICATORS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vocabulary {
+    pub modal_words: Vec<String>,
+    pub action_verbs: Vec<String>,
+    pub cost_indicators: Vec<String>,
+}
+
+impl Vocabulary {
+    /// Normalizes entries to trimmed lowercase, dropping blanks and duplicates.
+    fn normalize<I, S>(words: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = Vec::new();
+        for word in words {
+            let word = word.as_ref().trim().to_lowercase();
+            if !word.is_empty() && !normalized.contains(&word) {
+                normalized.push(word);
+            }
+        }
+        normalized
+    }
+
+    /// Appends normalized entries that are not already present.
+    fn extend<I, S>(list: &mut Vec<String>, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in Self::normalize(words) {
+            if !list.contains(&word) {
+                list.push(word);
+            }
+        }
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Vocabulary {
+            modal_words: Self::normalize(MODAL_WORDS),
+            action_verbs: Self::normalize(ACTION_VERBS),
+            cost_indicators: Self::normalize(COST_INDICATORS),
+        }
+    }
+}
+
+/// Policy compiler with deterministic output guarantees.
+/// 
+/// # Determinism
+/// Given identical input, the compiler produces identical output. All internal
+/// collections use deterministic ordering (BTreeMap over HashMap for iteration).
+#[derive(Debug, Clone, Default)]
+pub struct PolicyCompiler {
+    // Private fields enforce construction through ::new() or ::builder()
+    diagnostic_mode: DiagnosticMode,
+    vocabulary: Vocabulary,
+    principals: PrincipalRegistry,
+}
+
+/// Builder for a `PolicyCompiler` with a customized configuration.
+///
+/// Vocabulary setters come in two forms: `modal_words` and friends replace the
+/// list, while `extend_modal_words` and friends add to it.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyCompilerBuilder {
+    diagnostic_mode: DiagnosticMode,
+    vocabulary: Vocabulary,
+    principals: PrincipalRegistry,
+}
+
+impl PolicyCompilerBuilder {
+    /// Sets how many diagnostics a compilation reports.
+    pub fn diagnostic_mode(mut self, mode: DiagnosticMode) -> Self {
+        self.diagnostic_mode = mode;
+        self
+    }
+
+    /// Replaces the modal words that make a clause non-deterministic.
+    pub fn modal_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.vocabulary.modal_words = Vocabulary::normalize(words);
+        self
+    }
+
+    /// Adds modal words to the current list.
+    pub fn extend_modal_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Vocabulary::extend(&mut self.vocabulary.modal_words, words);
+        self
+    }
+
+    /// Replaces the action verbs a clause must contain.
+    pub fn action_verbs<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.vocabulary.action_verbs = Vocabulary::normalize(words);
+        self
+    }
+
+    /// Adds action verbs to the current list.
+    pub fn extend_action_verbs<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Vocabulary::extend(&mut self.vocabulary.action_verbs, words);
+        self
+    }
+
+    /// Replaces the terms that mark a clause as a cost clause.
+    pub fn cost_indicators<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.vocabulary.cost_indicators = Vocabulary::normalize(words);
+        self
+    }
+
+    /// Adds cost indicator terms to the current list.
+    pub fn extend_cost_indicators<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Vocabulary::extend(&mut self.vocabulary.cost_indicators, words);
+        self
+    }
+
+    /// Declares a named principal the compiler should recognize in clauses.
+    pub fn principal(mut self, name: &str, kind: Principal) -> Self {
+        self.principals.register(name, kind);
+        self
+    }
+
+    /// Replaces the principal registry.
+    pub fn principal_registry(mut self, registry: PrincipalRegistry) -> Self {
+        self.principals = registry;
+        self
+    }
+
+    /// Builds the configured compiler.
+    pub fn build(self) -> PolicyCompiler {
+        PolicyCompiler {
+            diagnostic_mode: self.diagnostic_mode,
+            vocabulary: self.vocabulary,
+            principals: self.principals,
+        }
+    }
+}
+
+impl PolicyCompiler {
+    /// Creates a new PolicyCompiler instance.
+    pub fn new() -> Self {
+        PolicyCompiler {
+            diagnostic_mode: DiagnosticMode::FailFast,
+            vocabulary: Vocabulary::default(),
+            principals: PrincipalRegistry::new(),
+        }
+    }
+
+    /// Creates a PolicyCompiler that reports diagnostics according to `mode`.
+    pub fn with_diagnostic_mode(mode: DiagnosticMode) -> Self {
+        Self::builder().diagnostic_mode(mode).build()
+    }
+
+    /// Returns a builder starting from the default configuration.
+    pub fn builder() -> PolicyCompilerBuilder {
+        PolicyCompilerBuilder::default()
+    }
+
+    /// Returns the diagnostic mode used by this compiler.
+    pub fn diagnostic_mode(&self) -> DiagnosticMode {
+        self.diagnostic_mode
+    }
+
+    /// Returns the vocabulary used by this compiler.
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    /// Returns the named principals this compiler recognizes.
+    pub fn principal_registry(&self) -> &PrincipalRegistry {
+        &self.principals
+    }
+
+    /// Compiles a policy string into governance artifacts.
+    ///
+    /// Natural-language clauses may be mixed with `clause` and `budget` declarations
+    /// (`clause access-log { principal SYSTEM; must log "access"; }`), which compile
+    /// to the same artifacts without free-text matching.
+    /// 
+    /// # Arguments
+    /// * `policy_input` - The policy text to compile
+    /// 
+    /// # Returns
+    /// A CompilationResult containing all artifacts or error details.
+    /// 
+    /// # Thread Safety
+    /// This method is stateless and inherently thread-safe.
+    /// Multiple threads can call compile() concurrently without synchronization.
+    pub fn compile(&self, policy_input: &str) -> CompilationResult {
+        if policy_input.trim().is_empty() {
+            return Self::fail_with_error(CompilationError::EmptyInput);
+        }
+
+        // Parse clauses
+        let (clauses, sections, declarations) = Self::parse_clauses(policy_input);
+        self.compile_clauses(&clauses, &sections, &declarations)
+    }
+
+    /// Compiles a Markdown policy document into governance artifacts.
+    ///
+    /// Headings open sections numbered by their nesting ("1", "1.2", ...), which
+    /// become the traced `clause_id`s. List items, table rows and paragraphs are
+    /// compiled as clauses; fenced code, HTML comments, blockquotes and table
+    /// headers are skipped. Spans point into `document`, so each traceability entry
+    /// names the Markdown line its clause came from.
+    pub fn compile_markdown(&self, document: &str) -> CompilationResult {
+        if document.trim().is_empty() {
+            return Self::fail_with_error(CompilationError::EmptyInput);
+        }
+
+        let (clauses, sections) = markdown::parse(document);
+        self.compile_clauses(&clauses, &sections, &Declarations::new())
+    }
+
+    /// Runs every compilation pass over parsed clauses.
+    fn compile_clauses(&self, clauses: &[Clause], sections: &[Section], declarations: &Declarations) -> CompilationResult {
+        if clauses.is_empty() {
+            return Self::fail_with_error(CompilationError::NoClauses);
+        }
+        // Local state for assumptions and exclusions
+        let mut assumptions = Vec::new();
+        let mut exclusions = Vec::new();
+
+        let report_all = self.diagnostic_mode == DiagnosticMode::ReportAll;
 
         // Normalize intent
-        let norm = match Self::normalize_intent(&clauses, &mut assumptions, &mut exclusions) {
-            Ok(n) => n,
-            Err(e) => return Self::fail_with_error(e),
+        let (norm, mut errors) = match self.normalize_intent(clauses, sections, declarations, &mut assumptions, &mut exclusions) {
+            Ok(n) => (Some(n), Vec::new()),
+            Err(mut errs) if !report_all => {
+                errs.truncate(1);
+                return Self::fail_with_errors(errs);
+            }
+            Err(errs) => (None, errs),
         };
 
         // Build artifact maps indexed by clause index using BTreeMap for deterministic iteration
         let mut dio_by_clause: BTreeMap<usize, Vec<DIOInvariant>> = BTreeMap::new();
         let mut auth_by_clause: BTreeMap<usize, Vec<ZTAuthority>> = BTreeMap::new();
         let mut cost_by_clause: BTreeMap<usize, Vec<ICAECostConstraint>> = BTreeMap::new();
+        let mut revocations_by_clause: BTreeMap<usize, Vec<RevocationRule>> = BTreeMap::new();
 
         // Initialize all clause indices
         for i in 0..clauses.len() {
@@ -311,23 +1560,72 @@ impl PolicyCompiler {
             cost_by_clause.insert(i, Vec::new());
         }
 
+        // Condition phrases become guards; the passes compile the rest of each clause.
+        // Declarations state their conditions explicitly.
+        let (guards, bodies): (Vec<Option<Guard>>, Vec<Clause>) = clauses.iter().enumerate()
+            .map(|(i, clause)| match declarations.get(&i) {
+                Some(declaration) => (declaration.as_ref().ok().and_then(|d| d.guard.clone()), clause.clone()),
+                None => self.split_guard(clause),
+            })
+            .unzip();
+
         // Compile artifacts - each step is deterministic
-        Self::compile_dio_invariants(&clauses, &mut dio_by_clause);
+        self.compile_dio_invariants(&bodies, declarations, &exclusions, &mut dio_by_clause);
         
-        let auth_errors = Self::compile_zt_authority(&clauses, &mut auth_by_clause);
-        if !auth_errors.is_empty() {
+        let auth_errors = self.compile_zt_authority(&bodies, declarations, &exclusions, &mut auth_by_clause);
+        if !auth_errors.is_empty() && !report_all {
             return Self::fail_with_errors(auth_errors);
         }
+        errors.extend(auth_errors);
+
+        let declaration_errors = self.compile_declarations(
+            clauses,
+            declarations,
+            &mut dio_by_clause,
+            &mut auth_by_clause,
+            &mut cost_by_clause,
+        );
+        if !declaration_errors.is_empty() && !report_all {
+            return Self::fail_with_errors(declaration_errors);
+        }
+        errors.extend(declaration_errors);
+
+        self.apply_revocations(&bodies, declarations, &mut auth_by_clause, &mut revocations_by_clause);
+
+        let conflict_errors = Self::detect_conflicts(&bodies, &guards, &auth_by_clause);
+        if !conflict_errors.is_empty() && !report_all {
+            return Self::fail_with_errors(conflict_errors);
+        }
+        errors.extend(conflict_errors);
+
+        let (delegation_graph, delegation_errors) = Self::compile_delegations(&bodies, declarations, &mut auth_by_clause);
+        if !delegation_errors.is_empty() && !report_all {
+            return Self::fail_with_errors(delegation_errors);
+        }
+        errors.extend(delegation_errors);
 
-        let cost_errors = Self::compile_icae_constraints(&clauses, &mut cost_by_clause);
-        if !cost_errors.is_empty() {
+        let cost_errors = self.compile_icae_constraints(&bodies, declarations, &exclusions, &mut cost_by_clause);
+        if !cost_errors.is_empty() && !report_all {
             return Self::fail_with_errors(cost_errors);
         }
+        errors.extend(cost_errors);
+
+        // Stable sort keeps pass order within each clause
+        let norm = match norm {
+            Some(n) if errors.is_empty() => n,
+            _ => {
+                errors.sort_by_key(|e| e.clause_index());
+                return Self::fail_with_errors(errors);
+            }
+        };
+
+        Self::apply_guards(clauses, &guards, &mut dio_by_clause, &mut auth_by_clause, &mut cost_by_clause);
 
         // Flatten artifacts in deterministic clause order
         let mut flattened_dio: Vec<DIOInvariant> = Vec::new();
         let mut flattened_auth: Vec<ZTAuthority> = Vec::new();
         let mut flattened_cost: Vec<ICAECostConstraint> = Vec::new();
+        let mut flattened_revocations: Vec<RevocationRule> = Vec::new();
 
         for i in 0..clauses.len() {
             if let Some(invariants) = dio_by_clause.get(&i) {
@@ -339,214 +1637,1273 @@ impl PolicyCompiler {
             if let Some(constraints) = cost_by_clause.get(&i) {
                 flattened_cost.extend(constraints.clone());
             }
+            if let Some(rules) = revocations_by_clause.get(&i) {
+                flattened_revocations.extend(rules.clone());
+            }
+        }
+
+        let traceability_map = Self::build_traceability_map(
+            clauses,
+            sections,
+            declarations,
+            &dio_by_clause,
+            &auth_by_clause,
+            &cost_by_clause,
+            &revocations_by_clause,
+        );
+
+        CompilationResult {
+            intent_normalization: norm,
+            dio_invariants: flattened_dio,
+            zt_authority_graph: flattened_auth,
+            delegation_graph,
+            icae_constraints: flattened_cost,
+            revocation_rules: flattened_revocations,
+            traceability_map,
+            verdict: CompilationStatus::Pass,
+            errors: Vec::new(),
+            #[allow(deprecated)]
+            failures: Vec::new(),
+        }
+    }
+
+    /// Parses policy text into individual clauses, the outline sections that number
+    /// them and the DSL declarations among them. Each declaration is one clause;
+    /// the text between declarations ends clauses at sentence-ending periods,
+    /// semicolons, blank lines and list items (see `segmenter`). Spans point into
+    /// `text`.
+    fn parse_clauses(text: &str) -> (Vec<Clause>, Vec<Section>, Declarations) {
+        let index = LineIndex::new(text);
+        let mut segments = Vec::new();
+        let mut declared = Vec::new();
+        let mut gap_start = 0;
+        for (start, end) in dsl::find_declarations(text).into_iter().chain(std::iter::once((text.len(), text.len()))) {
+            let shift = |(a, b): (usize, usize)| (gap_start + a, gap_start + b);
+            segments.extend(segmenter::segment(&text[gap_start..start]).into_iter().map(|segment| segmenter::Segment {
+                start: gap_start + segment.start,
+                end: gap_start + segment.end,
+                number: segment.number.map(shift),
+            }));
+            if start < end {
+                declared.push(segments.len());
+                segments.push(segmenter::Segment { start, end, number: None });
+            }
+            gap_start = end;
+        }
+
+        let mut clauses = Vec::new();
+        let mut sections: Vec<Section> = Vec::new();
+        let mut declarations = Declarations::new();
+
+        for (k, segment) in segments.iter().enumerate() {
+            let clause = Clause {
+                text: text[segment.start..segment.end].to_string(),
+                span: index.span(segment.start, segment.end),
+            };
+            if declared.contains(&k) {
+                declarations.insert(clauses.len(), dsl::parse(&clause.text));
+            }
+            let (start, end) = match segment.number {
+                Some(range) => range,
+                None => {
+                    clauses.push(clause);
+                    continue;
+                }
+            };
+
+            // The first clause of an item opens its section
+            if k == 0 || segments[k - 1].number != segment.number {
+                let number = &text[start..end];
+                let prefix = format!("{}.", number);
+                let has_subsections = segments.iter().any(|other| {
+                    matches!(other.number, Some((start, end)) if text[start..end].starts_with(&prefix))
+                });
+                let parent = sections.iter()
+                    .map(|section| section.number.as_str())
+                    .filter(|candidate| number.starts_with(&format!("{}.", candidate)))
+                    .max_by_key(|candidate| candidate.len())
+                    .map(str::to_string);
+                sections.push(Section {
+                    number: number.to_string(),
+                    heading: has_subsections.then(|| clause.text.clone()),
+                    parent,
+                    clause_indices: Vec::new(),
+                    span: index.span(start, end),
+                });
+                if has_subsections {
+                    continue;
+                }
+            }
+            if let Some(section) = sections.last_mut() {
+                section.clause_indices.push(clauses.len());
+            }
+            clauses.push(clause);
+        }
+
+        (clauses, sections, declarations)
+    }
+
+    /// Normalizes intent by validating clause structure and extracting assumptions/exclusions.
+    fn normalize_intent(
+        &self,
+        clauses: &[Clause],
+        sections: &[Section],
+        declarations: &Declarations,
+        assumptions: &mut Vec<Assumption>,
+        exclusions: &mut Vec<Exclusion>,
+    ) -> Result<IntentNormalization, Vec<CompilationError>> {
+        // Errors are collected pass by pass; the caller decides whether to keep only the first
+        let mut errors = Vec::new();
+
+        // Declarations must parse and have distinct names, which become their clause
+        // ids; the language checks below cover the other clauses
+        for (&i, declaration) in declarations {
+            let declared_before = |name: &str| {
+                declarations.range(..i).any(|(_, earlier)| matches!(earlier, Ok(earlier) if earlier.name == name))
+            };
+            let (message, (start, end)) = match declaration {
+                Err(error) => (error.message.clone(), error.range),
+                Ok(declaration) if declared_before(&declaration.name) => {
+                    (format!("'{}' is already declared", declaration.name), declaration.name_range)
+                }
+                Ok(_) => continue,
+            };
+            let clause = &clauses[i];
+            errors.push(CompilationError::InvalidDeclaration {
+                clause_index: i,
+                clause: clause.text.clone(),
+                message,
+                span: clause.subspan(start, end),
+            });
+        }
+
+        // Section numbers identify their clauses in the traceability map
+        for (k, section) in sections.iter().enumerate() {
+            if sections[..k].iter().any(|earlier| earlier.number == section.number) {
+                errors.push(CompilationError::DuplicateSection { number: section.number.clone(), span: section.span });
+            }
+        }
+
+        // Check for modal/discretionary language
+        for (i, clause) in text_clauses(clauses, declarations) {
+            let tokens = tokenize(&clause.text);
+            for modal_word in &self.vocabulary.modal_words {
+                if let Some((start, end)) = find_phrase(&tokens, modal_word) {
+                    errors.push(CompilationError::ModalLanguageDetected {
+                        clause_index: i,
+                        clause: clause.text.clone(),
+                        modal_word: modal_word.clone(),
+                        span: clause.subspan(start, end),
+                    });
+                }
+            }
+        }
+
+        // A leading condition must end at a comma or the clause's principal, or it
+        // would be compiled as part of the clause
+        for (i, clause) in text_clauses(clauses, declarations) {
+            let unterminated = guard::leads_with_condition(&clause.text)
+                && RevocationTrigger::from_clause(&clause.text).is_empty()
+                && guard::find_guard(&clause.text, &self.principals).is_none();
+            if unterminated {
+                errors.push(CompilationError::UnterminatedCondition {
+                    clause_index: i,
+                    clause: clause.text.clone(),
+                    span: clause.span,
+                });
+            }
+        }
+
+        // Check for atomic clauses
+        for (i, clause) in text_clauses(clauses, declarations) {
+            if !self.has_action_verb(&clause.text) {
+                errors.push(CompilationError::MissingActionVerb {
+                    clause_index: i,
+                    clause: clause.text.clone(),
+                    span: clause.span,
+                });
+            }
+        }
+
+        // Check for multiple actions without ordering
+        for (i, clause) in text_clauses(clauses, declarations) {
+            let tokens = tokenize(&clause.text);
+            // Conjunctions inside a condition or exclusion phrase combine conditions or
+            // excluded targets, not actions
+            let phrases: Vec<(usize, usize)> = guard::find_guard(&clause.text, &self.principals).map(|(_, range)| range)
+                .into_iter()
+                .chain(exclusion::find_exclusion_targets(&clause.text).map(|(range, _)| range))
+                .collect();
+            let in_phrase = |offset: usize| phrases.iter().any(|(start, end)| (*start..*end).contains(&offset));
+            // Check for conjunctions that indicate multiple unordered actions
+            let conjunction = tokens.iter().find(|t| (t.lower == "and" || t.lower == "or") && !in_phrase(t.offset));
+            if let Some(conjunction) = conjunction {
+                // Allow ordered sequences
+                if !contains_word(&tokens, &["then", "before", "after"]) {
+                    errors.push(CompilationError::AmbiguousMultiAction {
+                        clause_index: i,
+                        clause: clause.text.clone(),
+                        span: clause.subspan(conjunction.offset, conjunction.end()),
+                    });
+                }
+            }
+        }
+
+        assumptions.clear();
+        exclusions.clear();
+
+        // Extract assumptions and exclusions from clauses; the compiler applies
+        // exclusions even when reporting every diagnostic of a failed normalization
+        for (i, clause) in text_clauses(clauses, declarations) {
+            if let Some((start, end)) = exclusion::find_assumption(&clause.text) {
+                assumptions.push(Assumption {
+                    clause_index: i,
+                    statement: clause.text[start..end].to_string(),
+                    span: clause.subspan(start, end),
+                });
+            }
+            exclusions.extend(self.extract_exclusions(i, clause));
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(IntentNormalization {
+            clauses: clauses.to_vec(),
+            sections: sections.to_vec(),
+            polarities: clauses.iter().enumerate()
+                .map(|(i, clause)| match declarations.get(&i) {
+                    Some(Ok(declaration)) => declaration.polarity(),
+                    _ => Polarity::from_clause(&clause.text),
+                })
+                .collect(),
+            assumptions: assumptions.clone(),
+            exclusions: exclusions.clone(),
+        })
+    }
+
+    /// Parses the exclusions a clause states: each target of its "except" or
+    /// "excluding" phrase, as a named principal or a resource, and the condition of
+    /// an "unless" or "except when" phrase.
+    fn extract_exclusions(&self, clause_index: usize, clause: &Clause) -> Vec<Exclusion> {
+        let mut exclusions = Vec::new();
+
+        if let Some((_, targets)) = exclusion::find_exclusion_targets(&clause.text) {
+            let mentions = self.principals.mentions(&clause.text);
+            for (start, end) in targets {
+                let mentioned = mentions.iter().find(|((m_start, m_end), _)| *m_start < end && *m_end > start);
+                let target = match mentioned {
+                    Some((_, principal)) => ExclusionTarget::Principal { identity: principal.identity.clone() },
+                    None => {
+                        let words: Vec<String> = tokenize(&clause.text[start..end]).into_iter().map(|t| t.lower).collect();
+                        ExclusionTarget::Resource { name: words.join("_") }
+                    }
+                };
+                exclusions.push(Exclusion { clause_index, target, span: clause.subspan(start, end) });
+            }
+        }
+
+        if let Some((Guard::Not { guard }, (start, end))) = guard::find_guard(&clause.text, &self.principals) {
+            exclusions.push(Exclusion {
+                clause_index,
+                target: ExclusionTarget::Condition { guard: *guard },
+                span: clause.subspan(start, end),
+            });
+        }
+
+        exclusions
+    }
+
+    /// Separates a clause's condition phrase, if any, from the rest of the clause.
+    fn split_guard(&self, clause: &Clause) -> (Option<Guard>, Clause) {
+        let (guard, (start, end)) = match guard::find_guard(&clause.text, &self.principals) {
+            Some(found) => found,
+            None => return (None, clause.clone()),
+        };
+        // The phrase either leads the clause or runs to its end
+        let (body_start, body_end) = if start == 0 { (end, clause.text.len()) } else { (0, start) };
+        let body = &clause.text[body_start..body_end];
+        let leading = body.len() - body.trim_start().len();
+        let body = body.trim().trim_end_matches(',').trim_end();
+        let body_start = body_start + leading;
+        let body = Clause {
+            text: body.to_string(),
+            span: clause.subspan(body_start, body_start + body.len()),
+        };
+        (Some(guard), body)
+    }
+
+    /// Attaches each clause's guard to its artifacts. Invariants keep the full
+    /// clause text, condition phrase included.
+    fn apply_guards(
+        clauses: &[Clause],
+        guards: &[Option<Guard>],
+        dio_by_clause: &mut BTreeMap<usize, Vec<DIOInvariant>>,
+        auth_by_clause: &mut BTreeMap<usize, Vec<ZTAuthority>>,
+        cost_by_clause: &mut BTreeMap<usize, Vec<ICAECostConstraint>>,
+    ) {
+        for (i, guard) in guards.iter().enumerate() {
+            for invariant in dio_by_clause.entry(i).or_default() {
+                invariant.clause_text = clauses[i].text.clone();
+                invariant.guard = guard.clone();
+            }
+            for authority in auth_by_clause.entry(i).or_default() {
+                authority.guard = guard.clone();
+            }
+            // Constraints keep the guard their exclusions set
+            for constraint in cost_by_clause.entry(i).or_default() {
+                constraint.guard = match (guard.clone(), constraint.guard.take()) {
+                    (Some(condition), Some(exclusion)) => Some(Guard::All { guards: vec![condition, exclusion] }),
+                    (condition, exclusion) => condition.or(exclusion),
+                };
+            }
+        }
+    }
+
+    /// Compiles DIO invariants for each clause.
+    /// Clauses without an identifiable scope yield no invariant; the authority pass
+    /// reports them as `MissingResource`. Predicates ignore events by the clause's
+    /// excluded principals or on its excluded resources. Revocation clauses yield none,
+    /// and cost clauses yield ICAE constraints instead.
+    fn compile_dio_invariants(
+        &self,
+        clauses: &[Clause],
+        declarations: &Declarations,
+        exclusions: &[Exclusion],
+        dio_by_clause: &mut BTreeMap<usize, Vec<DIOInvariant>>,
+    ) {
+        for (i, clause) in text_clauses(clauses, declarations) {
+            if self.revocation(&clause.text).is_some() || self.is_cost_clause(&clause.text) {
+                continue;
+            }
+            let (steps, precedence, predicate) = match self.compile_sequence(&clause.text, i) {
+                Some(sequence) => sequence,
+                None => match self.compile_predicate(&clause.text) {
+                    Some(predicate) => (Vec::new(), Vec::new(), predicate),
+                    None => continue,
+                },
+            };
+            let mut principals = Vec::new();
+            let mut resources = Vec::new();
+            for exclusion in exclusions.iter().filter(|exclusion| exclusion.clause_index == i) {
+                match &exclusion.target {
+                    ExclusionTarget::Principal { identity } => principals.push(identity.clone()),
+                    ExclusionTarget::Resource { name } => resources.push(name.clone()),
+                    ExclusionTarget::Condition { .. } => {}
+                }
+            }
+            let predicate = if principals.is_empty() && resources.is_empty() {
+                predicate
+            } else {
+                InvariantPredicate::Excluding { predicate: Box::new(predicate), principals, resources }
+            };
+            let invariant_id = format!("dio_{}", i);
+            let truncated_clause = Self::truncate_clause(&clause.text, 50);
+            let description = format!("Enforce policy clause: {}", truncated_clause);
+            let failure_signal = format!("VIOLATION_DIO_{}", i);
+            
+            let invariant = DIOInvariant {
+                id: invariant_id.clone(),
+                description,
+                clause_index: i,
+                clause_text: clause.text.clone(),
+                steps,
+                precedence,
+                polarity: Polarity::from_clause(&clause.text),
+                predicate,
+                guard: None,
+                failure_signal,
+            };
+            
+            if let Some(list) = dio_by_clause.get_mut(&i) {
+                list.push(invariant);
+            }
+        }
+    }
+
+    /// Compiles the predicate a single-step clause imposes on execution traces.
+    fn compile_predicate(&self, clause: &str) -> Option<InvariantPredicate> {
+        let scope = self.extract_scope(clause)?;
+        let pattern = EventPattern { action: scope.action, resource: Some(scope.resource) };
+        Some(Self::all_of(Self::occurrence_predicates(clause, &pattern)))
+    }
+
+    /// Splits an ordered multi-step clause into steps, numbered `dio_{i}_step_{k}` in
+    /// text order, and the precedence edges between neighbouring steps. "A then B"
+    /// and "A before B" order A before B, and "A after B" orders B before A.
+    ///
+    /// The first step and steps joined by "then" are obligations: they must occur
+    /// as their own clause text requires. Steps introduced by "before" or "after"
+    /// only constrain the order. Returns `None` unless the clause has at least two
+    /// steps and no step is empty.
+    fn compile_sequence(&self, clause: &str, clause_index: usize) -> Option<(Vec<InvariantStep>, Vec<PrecedenceEdge>, InvariantPredicate)> {
+        let tokens = tokenize(clause);
+        let ranges = step_ranges(clause, &tokens);
+        if ranges.len() < 2 {
+            return None;
+        }
+
+        let mut steps: Vec<InvariantStep> = Vec::new();
+        let mut predicates = Vec::new();
+        let mut precedence = Vec::new();
+        for (k, (start, end, marker)) in ranges.into_iter().enumerate() {
+            let text = clause[start..end].trim_end_matches(|c: char| c == ',' || c == ';' || c.is_whitespace());
+            let text = text.strip_suffix(" and").unwrap_or(text).trim_end_matches(',').trim();
+            let pattern = self.step_pattern(text, steps.last().map(|step| &step.pattern))?;
+
+            if matches!(marker, None | Some("then")) {
+                predicates.extend(Self::occurrence_predicates(text, &pattern));
+            }
+            let id = format!("dio_{}_step_{}", clause_index, k);
+            if let Some(prior) = steps.last() {
+                let (before, after) = match marker {
+                    Some("after") => (id.clone(), prior.id.clone()),
+                    _ => (prior.id.clone(), id.clone()),
+                };
+                precedence.push(PrecedenceEdge { before, after });
+            }
+            steps.push(InvariantStep { id, text: text.to_string(), pattern });
+        }
+
+        let pattern_of = |id: &str| steps.iter().find(|step| step.id == id).map(|step| step.pattern.clone());
+        for edge in &precedence {
+            predicates.push(InvariantPredicate::Precedes { before: pattern_of(&edge.before)?, after: pattern_of(&edge.after)? });
+        }
+        Some((steps, precedence, Self::all_of(predicates)))
+    }
+
+    /// Returns the events a step selects: its scope, or its first verb (else its first
+    /// word) past any principal and auxiliaries when it names no resource. Pronouns ("them", "it") resolve to the
+    /// previous step's resource.
+    fn step_pattern(&self, text: &str, previous: Option<&EventPattern>) -> Option<EventPattern> {
+        match self.extract_scope(text) {
+            Some(scope) if STEP_PRONOUNS.contains(&scope.resource.as_str()) => Some(EventPattern {
+                action: scope.action,
+                resource: previous.and_then(|p| p.resource.clone()),
+            }),
+            Some(scope) => Some(EventPattern { action: scope.action, resource: Some(scope.resource) }),
+            None => {
+                let principals = self.principals.mentions(text);
+                let tokens = tokenize(text);
+                let words: Vec<&Token<'_>> = tokens.iter()
+                    .filter(|t| !AUXILIARY_WORDS.contains(&t.lower.as_str()))
+                    .filter(|t| !principals.iter().any(|((start, end), _)| t.offset < *end && t.end() > *start))
+                    .collect();
+                let action = words.iter().find_map(|t| self.base_verb(&t.lower))
+                    .or_else(|| words.first().map(|t| t.lower.clone()))?;
+                Some(EventPattern { action, resource: None })
+            }
+        }
+    }
+
+    /// Returns the occurrence predicates `text` imposes on events matching `pattern`.
+    ///
+    /// A prohibition forbids them and a permission allows any number; an obligation
+    /// requires at least one. "once", "twice", "exactly N times", "at least N times"
+    /// or "at most N times" set explicit counts for obligations and permissions.
+    /// "including a, b" requires fields on every matching event.
+    fn occurrence_predicates(text: &str, pattern: &EventPattern) -> Vec<InvariantPredicate> {
+        let tokens = tokenize(text);
+
+        let (min, max) = match Polarity::from_clause(text) {
+            Polarity::Prohibition => (None, Some(0)),
+            Polarity::Permission => Self::count_bounds(&tokens).unwrap_or((None, None)),
+            Polarity::Obligation => Self::count_bounds(&tokens).unwrap_or((Some(1), None)),
+        };
+        let mut predicates = vec![InvariantPredicate::Count { pattern: pattern.clone(), min, max }];
+
+        if let Some(k) = tokens.iter().position(|t| t.lower == "including" || t.lower == "containing") {
+            // Fields are separated by commas in the source text
+            let mut fields: Vec<Vec<&str>> = Vec::new();
+            let mut previous_end = None;
+            for token in tokens[k + 1..].iter().take_while(|t| !SCOPE_BOUNDARIES.contains(&t.lower.as_str())) {
+                let separated = previous_end.iter().all(|end| text[*end..token.offset].contains(','));
+                if separated {
+                    fields.push(Vec::new());
+                }
+                if let Some(field) = fields.last_mut() {
+                    field.push(token.lower.as_str());
+                }
+                previous_end = Some(token.end());
+            }
+            if !fields.is_empty() {
+                let fields = fields.into_iter().map(|words| words.join("_")).collect();
+                predicates.push(InvariantPredicate::RequiredFields { pattern: pattern.clone(), fields });
+            }
         }
 
-        let traceability_map = Self::build_traceability_map(
-            &clauses,
-            &dio_by_clause,
-            &auth_by_clause,
-            &cost_by_clause
-        );
+        predicates
+    }
 
-        CompilationResult {
-            intent_normalization: norm,
-            dio_invariants: flattened_dio,
-            zt_authority_graph: flattened_auth,
-            icae_constraints: flattened_cost,
-            traceability_map,
-            verdict: CompilationStatus::Pass,
-            errors: Vec::new(),
-            #[allow(deprecated)]
-            failures: Vec::new(),
-        }
+    /// Combines predicates that must all hold, without wrapping a single one.
+    fn all_of(mut predicates: Vec<InvariantPredicate>) -> InvariantPredicate {
+        if predicates.len() == 1 { predicates.remove(0) } else { InvariantPredicate::All { predicates } }
     }
 
-    /// Parses policy text into individual clauses.
-    /// Uses period as delimiter with whitespace normalization.
-    fn parse_clauses(text: &str) -> Vec<String> {
-        text.split_terminator('.')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect()
+    /// Parses an explicit occurrence count: "once", "twice", "exactly N times",
+    /// "at least N times" or "at most N times".
+    fn count_bounds(tokens: &[Token<'_>]) -> Option<(Option<u64>, Option<u64>)> {
+        let times = |k: usize| -> Option<u64> {
+            let count = parse_quantity(tokens.get(k)?.text).filter(|n| n.fract() == 0.0 && *n >= 0.0)?;
+            is_inflection_of(&tokens.get(k + 1)?.lower, "time").then_some(count as u64)
+        };
+        tokens.iter().enumerate().find_map(|(k, token)| match token.lower.as_str() {
+            "once" => Some((Some(1), Some(1))),
+            "twice" => Some((Some(2), Some(2))),
+            "exactly" => times(k + 1).map(|n| (Some(n), Some(n))),
+            "at" => match tokens.get(k + 1).map(|t| t.lower.as_str()) {
+                Some("least") => times(k + 2).map(|n| (Some(n), None)),
+                Some("most") => times(k + 2).map(|n| (None, Some(n))),
+                _ => None,
+            },
+            _ => None,
+        })
     }
 
-    /// Normalizes intent by validating clause structure and extracting assumptions/exclusions.
-    fn normalize_intent(
-        clauses: &[String],
-        assumptions: &mut Vec<String>,
-        exclusions: &mut Vec<String>,
-    ) -> Result<IntentNormalization, CompilationError> {
-        // Check for modal/discretionary language
-        for (i, clause) in clauses.iter().enumerate() {
-            let clause_lower = clause.to_lowercase();
-            for modal_word in MODAL_WORDS {
-                if clause_lower.contains(modal_word) {
-                    return Err(CompilationError::ModalLanguageDetected {
+    /// Compiles zero-trust authority graph for each clause.
+    ///
+    /// Every principal named in a clause yields one authority edge per role it plays.
+    /// A clause with a single edge keeps the id `zt_auth_{i}`; otherwise edges are
+    /// numbered `zt_auth_{i}_{k}` in text order. Principals the clause excludes get
+    /// no edge.
+    fn compile_zt_authority(
+        &self,
+        clauses: &[Clause],
+        declarations: &Declarations,
+        exclusions: &[Exclusion],
+        auth_by_clause: &mut BTreeMap<usize, Vec<ZTAuthority>>,
+    ) -> Vec<CompilationError> {
+        let mut errors = Vec::new();
+
+        for (i, clause) in text_clauses(clauses, declarations) {
+            // Revocation clauses bound other clauses' authorities; see `apply_revocations`
+            if self.revocation(&clause.text).is_some() {
+                continue;
+            }
+            let tokens = tokenize(&clause.text);
+            let excluded = |identity: &str| exclusions.iter().any(|exclusion| {
+                exclusion.clause_index == i
+                    && matches!(&exclusion.target, ExclusionTarget::Principal { identity: excluded } if excluded == identity)
+            });
+            let mentions: Vec<_> = self.principals.mentions(&clause.text)
+                .into_iter()
+                .filter(|(_, principal)| !excluded(&principal.identity))
+                .collect();
+            if mentions.is_empty() {
+                errors.push(CompilationError::MissingPrincipal {
+                    clause_index: i,
+                    clause: clause.text.clone(),
+                    span: clause.span,
+                });
+                continue;
+            }
+            // Cost clauses govern their cost subject; a missing subject is reported by
+            // the ICAE pass, and a missing verb by intent normalization
+            let is_cost = self.is_cost_clause(&clause.text);
+            let scope = if is_cost { self.cost_scope(&clause.text) } else { self.extract_scope(&clause.text) };
+            let scope = match scope {
+                Some(scope) => scope,
+                None if is_cost || !self.has_action_verb(&clause.text) => continue,
+                None => {
+                    errors.push(CompilationError::MissingResource {
                         clause_index: i,
-                        clause: clause.clone(),
-                        modal_word: modal_word.to_string(),
+                        clause: clause.text.clone(),
+                        span: clause.span,
                     });
+                    continue;
+                }
+            };
+
+            // Token index range covered by each mention
+            let bounds: Vec<(usize, usize)> = mentions
+                .iter()
+                .map(|((start, end), _)| {
+                    let first = tokens.iter().position(|t| t.offset >= *start).unwrap_or(0);
+                    let last = tokens.iter().rposition(|t| t.end() <= *end).unwrap_or(first);
+                    (first, last)
+                })
+                .collect();
+
+            // Roles are bound within each step of an ordered clause: "SYSTEM must log
+            // access before USER exports reports" has one principal per step
+            let steps = step_ranges(&clause.text, &tokens);
+            let step_of = |token: usize| {
+                let offset = tokens.get(token).map_or(clause.text.len(), |t| t.offset);
+                steps.iter().rposition(|(start, _, _)| *start <= offset).unwrap_or(0)
+            };
+            let step_bounds = |step: usize| {
+                let (start, end, _) = steps[step];
+                let first = tokens.iter().position(|t| t.offset >= start).unwrap_or(tokens.len());
+                let next = tokens.iter().position(|t| t.offset >= end).unwrap_or(tokens.len());
+                (first, next)
+            };
+
+            let mut edges: Vec<(NamedPrincipal, AuthorityRole)> = Vec::new();
+            for (k, ((start, end), principal)) in mentions.iter().enumerate() {
+                let (first, last) = bounds[k];
+                let step = step_of(first);
+                let (step_start, step_end) = step_bounds(step);
+                let prev_end = if k == 0 { 0 } else { bounds[k - 1].1 + 1 }.max(step_start);
+                let next_start = bounds.get(k + 1).map_or(tokens.len(), |b| b.0).min(step_end);
+                let alone = bounds.iter().filter(|(other, _)| step_of(*other) == step).count() == 1;
+
+                let role = match self.principal_role(&tokens, first, last, prev_end, next_start) {
+                    Some(role) => role,
+                    None if alone => AuthorityRole::Actor,
+                    None => {
+                        errors.push(CompilationError::AmbiguousPrincipalRole {
+                            clause_index: i,
+                            clause: clause.text.clone(),
+                            principal: principal.identity.clone(),
+                            span: clause.subspan(*start, *end),
+                        });
+                        continue;
+                    }
+                };
+                if !edges.iter().any(|(p, r)| p.identity == principal.identity && *r == role) {
+                    edges.push((principal.clone(), role));
                 }
             }
-        }
 
-        // Check for atomic clauses
-        for (i, clause) in clauses.iter().enumerate() {
-            let clause_lower = clause.to_lowercase();
-            let has_action = ACTION_VERBS.iter().any(|verb| clause_lower.contains(verb));
-            if !has_action {
-                return Err(CompilationError::MissingActionVerb {
+            let single = edges.len() == 1;
+            for (k, (NamedPrincipal { identity, kind: principal }, role)) in edges.into_iter().enumerate() {
+                let suffix = if single { i.to_string() } else { format!("{}_{}", i, k) };
+                let revocation_triggers: Vec<RevocationTrigger> = std::iter::once(RevocationTrigger::PolicyChange)
+                    .chain(RevocationTrigger::from_clause(&clause.text))
+                    .collect();
+
+                let authority = ZTAuthority {
+                    id: format!("zt_auth_{}", suffix),
+                    principal,
+                    identity,
+                    role,
+                    scope: scope.clone(),
+                    effect: AccessEffect::from_clause(&clause.text),
+                    polarity: Polarity::from_clause(&clause.text),
                     clause_index: i,
-                    clause: clause.clone(),
-                });
+                    delegation_rules: Vec::new(),
+                    revocation_triggers,
+                    guard: None,
+                    failure_signal: format!("VIOLATION_ZT_{}", suffix),
+                };
+
+                if let Some(list) = auth_by_clause.get_mut(&i) {
+                    list.push(authority);
+                }
             }
         }
 
-        // Check for multiple actions without ordering
-        for (i, clause) in clauses.iter().enumerate() {
-            let clause_lower = clause.to_lowercase();
-            // Check for conjunctions that indicate multiple unordered actions
-            if (clause_lower.contains(" and ") || clause_lower.contains(" or ")) 
-                && !clause_lower.contains("then")  // Allow ordered sequences
-                && !clause_lower.contains("before") 
-                && !clause_lower.contains("after") {
-                return Err(CompilationError::AmbiguousMultiAction {
+        errors
+    }
+
+    /// Compiles DSL declarations into the artifacts their natural-language
+    /// counterparts yield. A `clause` yields an invariant counting its action on its
+    /// resource and an authority per principal; a `budget` yields a cost constraint
+    /// and, per principal, an authority prohibiting it from exceeding the budget.
+    /// Principals must be built-in or registered.
+    fn compile_declarations(
+        &self,
+        clauses: &[Clause],
+        declarations: &Declarations,
+        dio_by_clause: &mut BTreeMap<usize, Vec<DIOInvariant>>,
+        auth_by_clause: &mut BTreeMap<usize, Vec<ZTAuthority>>,
+        cost_by_clause: &mut BTreeMap<usize, Vec<ICAECostConstraint>>,
+    ) -> Vec<CompilationError> {
+        let mut errors = Vec::new();
+
+        for (&i, declaration) in declarations {
+            // Syntax errors are reported by intent normalization
+            let declaration = match declaration {
+                Ok(declaration) => declaration,
+                Err(_) => continue,
+            };
+            let clause = &clauses[i];
+            let polarity = declaration.polarity();
+
+            let scope = match &declaration.rule {
+                dsl::Rule::Clause { action, resource, .. } => {
+                    let pattern = EventPattern { action: action.clone(), resource: Some(resource.clone()) };
+                    let (min, max) = match polarity {
+                        Polarity::Prohibition => (None, Some(0)),
+                        Polarity::Permission => (None, None),
+                        Polarity::Obligation => (Some(1), None),
+                    };
+                    dio_by_clause.entry(i).or_default().push(DIOInvariant {
+                        id: format!("dio_{}", i),
+                        description: format!("Enforce policy clause: {}", declaration.name),
+                        clause_index: i,
+                        clause_text: clause.text.clone(),
+                        steps: Vec::new(),
+                        precedence: Vec::new(),
+                        polarity,
+                        predicate: InvariantPredicate::Count { pattern, min, max },
+                        guard: None,
+                        failure_signal: format!("VIOLATION_DIO_{}", i),
+                    });
+                    AuthorityScope { action: action.clone(), resource: resource.clone() }
+                }
+                dsl::Rule::Budget { subject, ceiling, unit, period } => {
+                    cost_by_clause.entry(i).or_default().push(ICAECostConstraint {
+                        id: format!("icae_{}", i),
+                        subject: subject.clone(),
+                        measurement_unit: *unit,
+                        polarity,
+                        clause_index: i,
+                        ceiling: Some(*ceiling),
+                        period: Some(*period),
+                        externalities: vec![format!("External cost from: {}", declaration.name)],
+                        guard: None,
+                        failure_signal: format!("VIOLATION_ICAE_{}", i),
+                    });
+                    AuthorityScope { action: "exceed".to_string(), resource: subject.to_lowercase() }
+                }
+            };
+
+            let mut edges: Vec<(NamedPrincipal, AuthorityRole)> = Vec::new();
+            for declared in &declaration.principals {
+                let principal = self.principals.get(&declared.name).cloned().or_else(|| {
+                    Principal::from_word(&declared.name.to_lowercase())
+                        .map(|kind| NamedPrincipal { identity: kind.as_str().to_string(), kind })
+                });
+                match principal {
+                    Some(principal) if !edges.iter().any(|(p, r)| p.identity == principal.identity && *r == declared.role) => {
+                        edges.push((principal, declared.role));
+                    }
+                    Some(_) => {}
+                    None => errors.push(CompilationError::InvalidDeclaration {
+                        clause_index: i,
+                        clause: clause.text.clone(),
+                        message: format!("unknown principal '{}'", declared.name),
+                        span: clause.subspan(declared.range.0, declared.range.1),
+                    }),
+                }
+            }
+
+            let single = edges.len() == 1;
+            for (k, (NamedPrincipal { identity, kind: principal }, role)) in edges.into_iter().enumerate() {
+                let suffix = if single { i.to_string() } else { format!("{}_{}", i, k) };
+                auth_by_clause.entry(i).or_default().push(ZTAuthority {
+                    id: format!("zt_auth_{}", suffix),
+                    principal,
+                    identity,
+                    role,
+                    scope: scope.clone(),
+                    effect: AccessEffect::from_clause(&scope.action),
+                    polarity,
                     clause_index: i,
-                    clause: clause.clone(),
+                    delegation_rules: Vec::new(),
+                    revocation_triggers: vec![RevocationTrigger::PolicyChange],
+                    guard: None,
+                    failure_signal: format!("VIOLATION_ZT_{}", suffix),
                 });
             }
         }
 
-        assumptions.clear();
-        exclusions.clear();
+        errors
+    }
 
-        // Extract assumptions and exclusions from clauses
-        for clause in clauses {
-            let clause_lower = clause.to_lowercase();
-            if clause_lower.contains("assumes") || clause_lower.contains("assuming") {
-                assumptions.push(clause.clone());
+    /// Determines the role of the principal at `tokens[first..=last]`. Only words in
+    /// `prev_end..next_start`, between the neighbouring mentions, are considered.
+    /// Returns `None` when the principal is neither followed by a verb nor introduced
+    /// by "by" or a delegating "to".
+    fn principal_role(&self, tokens: &[Token<'_>], first: usize, last: usize, prev_end: usize, next_start: usize) -> Option<AuthorityRole> {
+        let is_any = |word: &str, verbs: &[&str]| verbs.iter().any(|verb| is_inflection_of(word, verb));
+
+        let before = &tokens[prev_end.min(first)..first];
+        match before.last().map(|t| t.lower.as_str()) {
+            // Passive agent: "approved by SYSTEM"
+            Some("by") => {
+                let verb = before.len().checked_sub(2).map(|k| before[k].lower.as_str());
+                return Some(verb.map_or(AuthorityRole::Actor, AuthorityRole::from_verb));
             }
-            if clause_lower.contains("except") || clause_lower.contains("exclude") || clause_lower.contains("unless") {
-                exclusions.push(clause.clone());
+            // Recipient of delegation: "delegate access to SERVICE"
+            Some("to") if before.iter().any(|t| is_any(&t.lower, DELEGATE_VERBS)) => {
+                return Some(AuthorityRole::Delegate);
             }
+            _ => {}
         }
 
-        Ok(IntentNormalization {
-            clauses: clauses.to_vec(),
-            assumptions: assumptions.clone(),
-            exclusions: exclusions.clone(),
-        })
+        let mut auxiliary = false;
+        for token in &tokens[last + 1..next_start.max(last + 1)] {
+            let word = token.lower.as_str();
+            if AUXILIARY_WORDS.contains(&word) {
+                auxiliary = true;
+                continue;
+            }
+            let known_verb = self.vocabulary.action_verbs.iter().any(|verb| is_inflection_of(word, verb))
+                || [APPROVER_VERBS, AUDITOR_VERBS, DELEGATE_VERBS, ALLOW_VERBS, DENY_VERBS]
+                    .iter()
+                    .any(|verbs| is_any(word, verbs));
+            return (auxiliary || known_verb).then(|| AuthorityRole::from_verb(word));
+        }
+        None
     }
 
-    /// Compiles DIO invariants for each clause.
-    fn compile_dio_invariants(clauses: &[String], dio_by_clause: &mut BTreeMap<usize, Vec<DIOInvariant>>) {
-        for (i, clause) in clauses.iter().enumerate() {
-            let invariant_id = format!("dio_{}", i);
-            let truncated_clause = Self::truncate_clause(clause, 50);
-            let description = format!("Enforce policy clause: {}", truncated_clause);
-            let failure_signal = format!("VIOLATION_DIO_{}", i);
-            
-            let invariant = DIOInvariant {
-                id: invariant_id.clone(),
-                description,
-                clause_index: i,
-                failure_signal,
+    /// Detects clauses with contradicting polarities (a prohibition against an
+    /// obligation or a permission) on the same scope for the same principal acting
+    /// in the same role under the same guard. Each conflict is reported once,
+    /// against the later clause.
+    fn detect_conflicts(
+        clauses: &[Clause],
+        guards: &[Option<Guard>],
+        auth_by_clause: &BTreeMap<usize, Vec<ZTAuthority>>,
+    ) -> Vec<CompilationError> {
+        let mut errors = Vec::new();
+        let authorities: Vec<&ZTAuthority> = auth_by_clause.values().flatten().collect();
+
+        for (k, later) in authorities.iter().enumerate() {
+            let earlier = authorities[..k].iter().find(|earlier| {
+                earlier.identity == later.identity
+                    && earlier.role == later.role
+                    && earlier.polarity.contradicts(later.polarity)
+                    && earlier.clause_index != later.clause_index
+                    && earlier.scope == later.scope
+                    && guards[earlier.clause_index] == guards[later.clause_index]
+            });
+            if let Some(earlier) = earlier {
+                let clause = &clauses[later.clause_index];
+                errors.push(CompilationError::ConflictingClauses {
+                    a: earlier.clause_index,
+                    b: later.clause_index,
+                    span: clause.span,
+                });
+            }
+        }
+
+        errors
+    }
+
+    /// Adds the triggers of each revocation clause to the authorities it revokes:
+    /// the non-prohibition edges of its principals (of every principal when it names
+    /// none) from other clauses, on its resource when it names one. A revocation
+    /// that matches no edge compiles to a standalone `RevocationRule`.
+    fn apply_revocations(
+        &self,
+        clauses: &[Clause],
+        declarations: &Declarations,
+        auth_by_clause: &mut BTreeMap<usize, Vec<ZTAuthority>>,
+        revocations_by_clause: &mut BTreeMap<usize, Vec<RevocationRule>>,
+    ) {
+        for (i, clause) in text_clauses(clauses, declarations) {
+            let revocation = match self.revocation(&clause.text) {
+                Some(revocation) => revocation,
+                None => continue,
             };
-            
-            if let Some(list) = dio_by_clause.get_mut(&i) {
-                list.push(invariant);
+            let mut matched = false;
+            for authority in auth_by_clause.values_mut().flatten() {
+                let targeted = authority.polarity != Polarity::Prohibition
+                    && (revocation.identities.is_empty() || revocation.identities.contains(&authority.identity))
+                    && !matches!(&revocation.resource, Some(resource) if *resource != authority.scope.resource);
+                if !targeted {
+                    continue;
+                }
+                matched = true;
+                for trigger in &revocation.triggers {
+                    if !authority.revocation_triggers.contains(trigger) {
+                        authority.revocation_triggers.push(*trigger);
+                    }
+                }
+            }
+            if !matched {
+                revocations_by_clause.insert(i, vec![RevocationRule {
+                    id: format!("revocation_{}", i),
+                    identities: revocation.identities,
+                    resource: revocation.resource,
+                    triggers: revocation.triggers,
+                    clause_index: i,
+                }]);
             }
         }
     }
 
-    /// Compiles zero-trust authority graph for each clause.
-    fn compile_zt_authority(clauses: &[String], auth_by_clause: &mut BTreeMap<usize, Vec<ZTAuthority>>) -> Vec<CompilationError> {
+    /// Compiles delegation edges from clauses naming a delegate ("SYSTEM delegates
+    /// audit authority to SERVICE") and rejects edges that close a cycle.
+    ///
+    /// Each actor in the clause delegates to each delegate; the scope is the text
+    /// between the delegating verb and "to". "depth N" sets `max_depth` and the
+    /// first "<number> <time unit>" sets `expiry`. Edges are attached to the
+    /// delegating authority's `delegation_rules`.
+    fn compile_delegations(
+        clauses: &[Clause],
+        declarations: &Declarations,
+        auth_by_clause: &mut BTreeMap<usize, Vec<ZTAuthority>>,
+    ) -> (DelegationGraph, Vec<CompilationError>) {
+        let mut graph = DelegationGraph::new();
         let mut errors = Vec::new();
 
-        for (i, clause) in clauses.iter().enumerate() {
-            match Principal::from_clause(clause) {
-                Some(principal) => {
-                    let authority_id = format!("zt_auth_{}", i);
-                    let scope = format!("scope_{}", i);
-                    let truncated_clause = Self::truncate_clause(clause, 30);
-                    
-                    let delegation_rules = vec![
-                        format!("Delegation requires explicit {} approval for: {}", principal, truncated_clause)
-                    ];
-                    let revocation_triggers = vec![
-                        format!("Revoke on policy change affecting: {}", truncated_clause)
-                    ];
-                    
-                    let authority = ZTAuthority {
-                        id: authority_id,
-                        principal,
-                        scope,
+        for (i, clause) in text_clauses(clauses, declarations) {
+            let authorities = match auth_by_clause.get_mut(&i) {
+                Some(list) => list,
+                None => continue,
+            };
+            let delegates: Vec<String> = authorities.iter()
+                .filter(|auth| auth.role == AuthorityRole::Delegate)
+                .map(|auth| auth.identity.clone())
+                .collect();
+            if delegates.is_empty() {
+                continue;
+            }
+
+            let tokens = tokenize(&clause.text);
+            let verb = tokens.iter().position(|t| DELEGATE_VERBS.iter().any(|verb| is_inflection_of(&t.lower, verb)));
+            let scope = verb.and_then(|v| {
+                let to = v + tokens[v..].iter().position(|t| t.lower == "to")?;
+                let words: Vec<&str> = tokens[v + 1..to].iter()
+                    .map(|t| t.lower.as_str())
+                    .filter(|word| !["the", "a", "an", "all", "any", "its", "their"].contains(word))
+                    .collect();
+                Some(words.join(" "))
+            });
+            let has_actor = authorities.iter().any(|auth| auth.role == AuthorityRole::Actor);
+            let scope = match scope {
+                Some(scope) if has_actor && !scope.is_empty() => scope,
+                _ => {
+                    errors.push(CompilationError::IncompleteDelegation {
+                        clause_index: i,
+                        clause: clause.text.clone(),
+                        span: clause.span,
+                    });
+                    continue;
+                }
+            };
+
+            let max_depth = tokens.iter().position(|t| t.lower == "depth").and_then(|d| {
+                let rest = &tokens[d + 1..];
+                let number = if rest.first()?.lower == "of" { rest.get(1)? } else { &rest[0] };
+                number.text.parse::<u32>().ok()
+            });
+            let expiry = TimeSpan::find_in_tokens(&tokens);
+
+            let mut k = 0;
+            let total = authorities.iter().filter(|auth| auth.role == AuthorityRole::Actor).count() * delegates.len();
+            for authority in authorities.iter_mut().filter(|auth| auth.role == AuthorityRole::Actor) {
+                for to in &delegates {
+                    let id = if total == 1 { format!("zt_deleg_{}", i) } else { format!("zt_deleg_{}_{}", i, k) };
+                    k += 1;
+                    let edge = DelegationEdge {
+                        id,
+                        from: authority.identity.clone(),
+                        to: to.clone(),
+                        scope: scope.clone(),
+                        max_depth,
+                        expiry,
                         clause_index: i,
-                        delegation_rules,
-                        revocation_triggers,
                     };
-                    
-                    if let Some(list) = auth_by_clause.get_mut(&i) {
-                        list.push(authority);
+
+                    // An edge closes a cycle if its target already reaches its source
+                    let cycle = if edge.from == edge.to {
+                        Some(vec![edge.from.clone(), edge.to.clone()])
+                    } else {
+                        graph.path(&edge.to, &edge.from).map(|path| {
+                            std::iter::once(edge.from.clone()).chain(path).collect()
+                        })
+                    };
+                    if let Some(cycle) = cycle {
+                        errors.push(CompilationError::DelegationCycle {
+                            clause_index: i,
+                            clause: clause.text.clone(),
+                            cycle,
+                            span: clause.span,
+                        });
+                        continue;
                     }
+
+                    authority.delegation_rules.push(edge.clone());
+                    graph.add_edge(edge);
                 }
-                None => {
-                    errors.push(CompilationError::MissingPrincipal {
-                        clause_index: i,
-                        clause: clause.clone(),
-                    });
+            }
+        }
+
+        (graph, errors)
+    }
+
+    /// Returns the base form of `word` if it inflects a known verb ("logged" -> "log").
+    fn base_verb(&self, word: &str) -> Option<String> {
+        self.vocabulary.action_verbs.iter().map(String::as_str)
+            .chain(ACCESS_VERBS.iter().copied())
+            .chain(APPROVER_VERBS.iter().copied())
+            .chain(AUDITOR_VERBS.iter().copied())
+            .chain(DELEGATE_VERBS.iter().copied())
+            .find(|verb| is_inflection_of(word, verb))
+            .map(str::to_string)
+    }
+
+    /// Returns the run of resource words starting at `start`, after any leading filler
+    /// or boundary words, and the index just past it.
+    fn scope_run(words: &[String], start: usize) -> (Vec<String>, usize) {
+        let first = start + words[start.min(words.len())..].iter()
+            .take_while(|word| SCOPE_FILLER.contains(&word.as_str()) || SCOPE_BOUNDARIES.contains(&word.as_str()))
+            .count();
+        let len = words[first.min(words.len())..].iter()
+            .take_while(|word| !SCOPE_BOUNDARIES.contains(&word.as_str()))
+            .count();
+        (words[first..first + len].to_vec(), first + len)
+    }
+
+    /// Parses a revocation clause: one whose verb is "revoke" and which states when,
+    /// such as "Revoke USER access after 30 days" or "SERVICE access to payroll must
+    /// be revoked on role change". The revoked principals follow an active verb and
+    /// precede a passive one; "Revoke on role change" names none and revokes every
+    /// principal. Returns `None` for other clauses, including grants that mention
+    /// their own revocation ("Allow USER access until revoked on role change").
+    fn revocation(&self, clause: &str) -> Option<Revocation> {
+        let triggers = RevocationTrigger::from_clause(clause);
+        if triggers.is_empty() {
+            return None;
+        }
+        let tokens = tokenize(clause);
+        let mentions = self.principals.mentions(clause);
+        let is_principal = |t: &Token<'_>| mentions.iter().any(|((start, end), _)| t.offset < *end && t.end() > *start);
+
+        let verb = tokens.iter()
+            .position(|t| !is_principal(t) && !AUXILIARY_WORDS.contains(&t.lower.as_str()) && self.base_verb(&t.lower).is_some())?;
+        if !is_inflection_of(&tokens[verb].lower, "revoke") {
+            return None;
+        }
+        let passive = matches!(tokens[..verb].last(), Some(t) if ["is", "are", "be"].contains(&t.lower.as_str()));
+        let verb_offset = tokens[verb].offset;
+        let identities: Vec<String> = mentions.iter()
+            .filter(|((start, _), _)| (*start < verb_offset) == passive)
+            .map(|(_, principal)| principal.identity.clone())
+            .collect();
+
+        let object = if passive {
+            let marker = tokens[..verb].iter().position(|t| PREDICATE_MARKERS.contains(&t.lower.as_str())).unwrap_or(verb);
+            &tokens[..marker]
+        } else {
+            let bound = RevocationTrigger::phrase_start(&tokens).unwrap_or(tokens.len()).max(verb + 1);
+            &tokens[verb + 1..bound]
+        };
+        let words: Vec<String> = object.iter()
+            .filter(|t| !is_principal(t) && parse_quantity(t.text).is_none())
+            .map(|t| t.lower.clone())
+            .collect();
+        // "access to payroll" names a resource; "access after 30 days" does not
+        let start = words.iter().take_while(|word| SCOPE_FILLER.contains(&word.as_str())).count();
+        let resource = match words.get(start).map(String::as_str) {
+            Some(word) if AUTHORITY_WORDS.contains(&word) => match words.get(start + 1).map(String::as_str) {
+                Some("to" | "of" | "for") => Self::scope_run(&words, start + 1).0,
+                _ => Vec::new(),
+            },
+            Some(word) if !SCOPE_BOUNDARIES.contains(&word) => Self::scope_run(&words, start).0,
+            _ => Vec::new(),
+        };
+        let resource: Vec<String> = resource.into_iter().filter(|word| !AUTHORITY_WORDS.contains(&word.as_str())).collect();
+
+        Some(Revocation {
+            identities,
+            resource: if resource.is_empty() { None } else { Some(resource.join("_")) },
+            triggers,
+        })
+    }
+
+    /// Returns whether a clause contains one of the vocabulary's action verbs, or a
+    /// permission modal followed by a verb.
+    fn has_action_verb(&self, clause: &str) -> bool {
+        let tokens = tokenize(clause);
+        // "can read", "can be read", "cannot export"
+        let permits = tokens.iter().enumerate().any(|(k, t)| {
+            let verb = tokens[k + 1..].iter().find(|t| !AUXILIARY_WORDS.contains(&t.lower.as_str()));
+            PERMISSION_MODALS.contains(&t.lower.as_str())
+                && matches!(verb, Some(v) if !SCOPE_BOUNDARIES.contains(&v.lower.as_str()) && parse_quantity(v.text).is_none())
+        });
+        permits || self.vocabulary.action_verbs.iter().any(|verb| find_term(&tokens, verb).is_some())
+    }
+
+    /// Locates the verb of a clause: the word after the predicate marker and any
+    /// auxiliaries or, without a marker, the first known verb of an imperative.
+    /// Returns the marker's index, the verb's index and whether the verb is passive.
+    fn find_verb<F>(&self, tokens: &[Token<'_>], is_principal: F) -> Option<(Option<usize>, usize, bool)>
+    where
+        F: Fn(&Token<'_>) -> bool,
+    {
+        let marker = tokens.iter().position(|t| PREDICATE_MARKERS.contains(&t.lower.as_str()));
+        match marker {
+            Some(m) => {
+                let mut v = m;
+                let mut passive = false;
+                while v < tokens.len() && AUXILIARY_WORDS.contains(&tokens[v].lower.as_str()) {
+                    passive |= ["is", "are", "be"].contains(&tokens[v].lower.as_str());
+                    v += 1;
                 }
+                Some((marker, v, passive))
+            }
+            None => {
+                let verb = tokens.iter().position(|t| !is_principal(t) && self.base_verb(&t.lower).is_some())?;
+                Some((None, verb, false))
             }
         }
-        
-        errors
+    }
+
+    /// Returns the scope of a cost clause: its verb on the cost subject, as in
+    /// `exceed:inference` for "Cost of inference must not exceed 100 USD per month".
+    fn cost_scope(&self, clause: &str) -> Option<AuthorityScope> {
+        let tokens = tokenize(clause);
+        let principals: Vec<(usize, usize)> = self.principals.mentions(clause).into_iter().map(|(range, _)| range).collect();
+        let is_principal = |t: &Token<'_>| principals.iter().any(|(start, end)| t.offset < *end && t.end() > *start);
+        let (_, verb, _) = self.find_verb(&tokens, is_principal)?;
+        let verb_word = &tokens.get(verb)?.lower;
+        Some(AuthorityScope {
+            action: self.base_verb(verb_word).unwrap_or_else(|| verb_word.clone()),
+            resource: self.cost_subject(clause)?.to_lowercase(),
+        })
+    }
+
+    /// Extracts the action and resource an authority clause governs. Returns `None`
+    /// when no resource can be identified.
+    fn extract_scope(&self, clause: &str) -> Option<AuthorityScope> {
+        let tokens = tokenize(clause);
+        // Trigger phrases say when authority ends, not what it covers: "on role change"
+        let bound = RevocationTrigger::phrase_start(&tokens).unwrap_or(tokens.len());
+        let tokens = &tokens[..bound];
+        let principals: Vec<(usize, usize)> = self.principals.mentions(clause).into_iter().map(|(range, _)| range).collect();
+        let is_principal = |t: &Token<'_>| principals.iter().any(|(start, end)| t.offset < *end && t.end() > *start);
+        let base_verb = |word: &str| self.base_verb(word);
+
+        let (marker, verb, passive) = self.find_verb(tokens, is_principal)?;
+        let verb_word = &tokens.get(verb)?.lower;
+        let verb_base = base_verb(verb_word).unwrap_or_else(|| verb_word.clone());
+
+        // Passive participles are followed by their agent: "access allowed by USER"
+        let passive = passive || matches!(tokens.get(verb + 1), Some(t) if t.lower == "by");
+        let object = match marker {
+            Some(m) if passive => &tokens[..m],
+            None if passive => &tokens[..verb],
+            _ => &tokens[verb + 1..],
+        };
+        // Assumption markers ("assumes that") and abbreviations ("e.g.") are not object words
+        let assumed = match tokens.first() {
+            Some(first) if is_inflection_of(&first.lower, "assume") => exclusion::find_assumption(clause).map_or(0, |(start, _)| start),
+            _ => 0,
+        };
+        let content = |slice: &'_ [Token<'_>]| -> Vec<String> {
+            slice.iter()
+                .filter(|t| !is_principal(t) && parse_quantity(t.text).is_none() && t.offset >= assumed)
+                .filter(|t| !(segmenter::is_abbreviation(&t.lower) && clause[t.end()..].starts_with('.')))
+                .map(|t| t.lower.clone())
+                .collect()
+        };
+        let words = content(object);
+
+        let (head, end) = Self::scope_run(&words, 0);
+        // Access verbs may name the action they govern: "allow export of data"
+        let tail = match words.get(end).map(String::as_str) {
+            Some("of" | "to" | "on" | "for") if ACCESS_VERBS.contains(&verb_base.as_str()) && !head.is_empty() => {
+                Self::scope_run(&words, end).0
+            }
+            _ => Vec::new(),
+        };
+        let (action, mut resource) = if tail.is_empty() { (verb_base, head) } else { (head.join("_"), tail) };
+        // Verbs without an object govern their subject: "Cost of logging cannot exceed ..."
+        if let (true, Some(m), false) = (resource.is_empty(), marker, passive) {
+            resource = Self::scope_run(&content(&tokens[..m]), 0).0;
+        }
+        if resource.is_empty() {
+            return None;
+        }
+
+        Some(AuthorityScope { action, resource: resource.join("_") })
+    }
+
+    /// Returns whether a clause compiles to a cost constraint: it mentions cost and
+    /// is not a revocation condition ("revoke when budget exceeded").
+    fn is_cost_clause(&self, clause: &str) -> bool {
+        let tokens = tokenize(clause);
+        self.vocabulary.cost_indicators.iter().any(|indicator| find_term(&tokens, indicator).is_some())
+            && !RevocationTrigger::from_clause(clause).contains(&RevocationTrigger::BudgetExceeded)
+    }
+
+    /// Returns the subject a cost clause attributes cost to. Cost measured in a unit
+    /// but naming no subject is the principal's own.
+    fn cost_subject(&self, clause: &str) -> Option<String> {
+        self.extract_subject(clause).or_else(|| {
+            MeasurementUnit::from_clause(clause)
+                .and_then(|_| self.principals.resolve(clause))
+                .map(|principal| principal.identity)
+        })
     }
 
     /// Compiles ICAE cost constraints for clauses mentioning cost.
-    fn compile_icae_constraints(clauses: &[String], cost_by_clause: &mut BTreeMap<usize, Vec<ICAECostConstraint>>) -> Vec<CompilationError> {
+    /// Cost events name no principal or resource, so excluded resources ("except for
+    /// testing") instead guard the constraint with their negation (`!testing`).
+    fn compile_icae_constraints(
+        &self,
+        clauses: &[Clause],
+        declarations: &Declarations,
+        exclusions: &[Exclusion],
+        cost_by_clause: &mut BTreeMap<usize, Vec<ICAECostConstraint>>,
+    ) -> Vec<CompilationError> {
         let mut errors = Vec::new();
 
-        for (i, clause) in clauses.iter().enumerate() {
-            let clause_lower = clause.to_lowercase();
-            let has_cost_mention = COST_INDICATORS.iter().any(|ind| clause_lower.contains(ind));
-
-            if !has_cost_mention {
+        for (i, clause) in text_clauses(clauses, declarations) {
+            if !self.is_cost_clause(&clause.text) {
                 continue;
             }
 
-            let subject = match Self::extract_subject(clause) {
+            let subject = match self.cost_subject(&clause.text) {
                 Some(s) => s,
                 None => {
                     errors.push(CompilationError::MissingCostSubject {
                         clause_index: i,
-                        clause: clause.clone(),
+                        clause: clause.text.clone(),
+                        span: clause.span,
                     });
                     continue;
                 }
             };
 
-            let measurement_unit = match MeasurementUnit::from_clause(clause) {
+            let measurement_unit = match MeasurementUnit::from_clause(&clause.text) {
                 Some(u) => u,
                 None => {
                     errors.push(CompilationError::MissingMeasurementUnit {
                         clause_index: i,
-                        clause: clause.clone(),
+                        clause: clause.text.clone(),
+                        span: clause.span,
+                    });
+                    continue;
+                }
+            };
+
+            let ceiling = match Self::extract_ceiling(&clause.text, measurement_unit) {
+                Ok(c) => c,
+                Err((start, end)) => {
+                    errors.push(CompilationError::MissingCeiling {
+                        clause_index: i,
+                        clause: clause.text.clone(),
+                        span: clause.subspan(start, end),
+                    });
+                    continue;
+                }
+            };
+
+            let period = match Self::extract_period(&clause.text, ceiling.is_some()) {
+                Ok(p) => p,
+                Err(range) => {
+                    errors.push(CompilationError::AmbiguousBudgetPeriod {
+                        clause_index: i,
+                        clause: clause.text.clone(),
+                        span: range.map(|(start, end)| clause.subspan(start, end)).unwrap_or(clause.span),
                     });
                     continue;
                 }
             };
 
             let constraint_id = format!("icae_{}", i);
-            let truncated_clause = Self::truncate_clause(clause, 30);
+            let truncated_clause = Self::truncate_clause(&clause.text, 30);
             let externalities = vec![format!("External cost from: {}", truncated_clause)];
 
             let constraint = ICAECostConstraint {
                 id: constraint_id,
                 subject,
                 measurement_unit,
+                polarity: Polarity::from_clause(&clause.text),
                 clause_index: i,
-                ceiling: None,
+                ceiling,
+                period,
                 externalities,
+                guard: Self::exclusion_guard(exclusions, i),
+                failure_signal: format!("VIOLATION_ICAE_{}", i),
             };
 
             if let Some(list) = cost_by_clause.get_mut(&i) {
@@ -557,35 +2914,122 @@ impl PolicyCompiler {
         errors
     }
 
-    /// Extracts cost attribution subject from clause.
-    fn extract_subject(clause: &str) -> Option<String> {
-        let stop_words: &[&str] = &[
-            "cost", "spend", "usage", "quota", "the", "a", "an", "of", "for",
-            "per", "must", "shall", "cannot", "exceed", "all", "no", "be", "by",
-            "system", "user", "service"
-        ];
-        
-        for word in clause.split_whitespace() {
-            let clean_word = word.trim_matches(|c: char| !c.is_alphanumeric());
-            let lower = clean_word.to_lowercase();
-            
-            if clean_word.len() > 3 && !stop_words.contains(&lower.as_str()) {
-                return Some(clean_word.to_string());
-            }
+    /// Returns the guard under which clause `clause_index` still applies given the
+    /// resources it excludes, as conditions: `!testing`, or `!(a || b)` for several.
+    fn exclusion_guard(exclusions: &[Exclusion], clause_index: usize) -> Option<Guard> {
+        let mut excluded: Vec<Guard> = exclusions
+            .iter()
+            .filter(|exclusion| exclusion.clause_index == clause_index)
+            .filter_map(|exclusion| match &exclusion.target {
+                ExclusionTarget::Resource { name } => Some(Guard::Condition { name: name.clone() }),
+                _ => None,
+            })
+            .collect();
+        let guard = match excluded.len() {
+            0 => return None,
+            1 => excluded.remove(0),
+            _ => Guard::Any { guards: excluded },
+        };
+        Some(Guard::Not { guard: Box::new(guard) })
+    }
+
+    /// Extracts the numeric ceiling from a cost clause that states a limit.
+    ///
+    /// The quantity adjacent to the measurement unit wins ("1000 USD", "USD 1000");
+    /// otherwise the first quantity after the limit term is used. Clauses without a
+    /// limit term have no ceiling. Returns the byte range of the limit term as the
+    /// error when a limit is stated without any number.
+    fn extract_ceiling(clause: &str, unit: MeasurementUnit) -> Result<Option<f64>, (usize, usize)> {
+        let tokens = tokenize(clause);
+        let limit = match LIMIT_INDICATORS.iter().find_map(|ind| find_term(&tokens, ind)) {
+            Some(range) => range,
+            None => return Ok(None),
+        };
+
+        let unit_name = unit.as_str();
+        let adjacent = tokens.iter().position(|t| t.text.eq_ignore_ascii_case(unit_name)).and_then(|pos| {
+            let before = pos.checked_sub(1).and_then(|k| tokens.get(k));
+            before.into_iter().chain(tokens.get(pos + 1))
+                .find_map(|t| parse_quantity(t.text))
+        });
+        let after_limit = || {
+            tokens.iter()
+                .filter(|t| t.offset >= limit.1)
+                .find_map(|t| parse_quantity(t.text))
+        };
+
+        match adjacent.or_else(after_limit) {
+            Some(value) => Ok(Some(value)),
+            None => Err(limit),
         }
-        None
+    }
+
+    /// Extracts the budget period of a cost clause.
+    ///
+    /// A clause with a ceiling must name exactly one distinct period; otherwise the
+    /// error carries the range of the first conflicting mention, or `None` when no
+    /// period is named at all. Clauses without a ceiling never fail.
+    fn extract_period(clause: &str, has_ceiling: bool) -> Result<Option<BudgetPeriod>, Option<(usize, usize)>> {
+        let mentions = BudgetPeriod::find_in_clause(clause);
+        let first = mentions.first().map(|(period, _)| *period);
+        let conflict = mentions.iter().find(|(period, _)| Some(*period) != first);
+
+        match (first, conflict) {
+            (Some(period), None) => Ok(Some(period)),
+            (Some(_), Some((_, range))) if has_ceiling => Err(Some(*range)),
+            (None, _) if has_ceiling => Err(None),
+            _ => Ok(None),
+        }
+    }
+
+    /// Extracts the cost attribution subject of a clause: the object of "of" or
+    /// "for" after its cost term ("Cost of inference"), else the word qualifying the
+    /// cost term ("Storage spend"). Quantities, measurement units, budget periods
+    /// and principals are never subjects.
+    fn extract_subject(&self, clause: &str) -> Option<String> {
+        let tokens = tokenize(clause);
+        let (start, end) = self.vocabulary.cost_indicators.iter().filter_map(|term| find_term(&tokens, term)).min()?;
+        let first = tokens.iter().position(|t| t.offset >= start)?;
+        let last = tokens.iter().rposition(|t| t.end() <= end)?;
+
+        let principals = self.principals.mentions(clause);
+        let is_subject = |t: &Token<'_>| {
+            let singular = t.lower.strip_suffix('s').unwrap_or(&t.lower);
+            parse_quantity(t.text).is_none()
+                && !MeasurementUnit::ALL.iter().any(|unit| {
+                    let unit = unit.as_str().to_lowercase();
+                    t.lower == unit || format!("{}s", t.lower) == unit
+                })
+                && BudgetPeriod::from_word(&t.lower).or_else(|| BudgetPeriod::from_word(singular)).is_none()
+                && !principals.iter().any(|((start, end), _)| t.offset < *end && t.end() > *start)
+        };
+
+        let object = match tokens.get(last + 1) {
+            Some(t) if t.lower == "of" || t.lower == "for" => {
+                tokens[last + 2..].iter().find(|t| !SCOPE_FILLER.contains(&t.lower.as_str()))
+            }
+            _ => None,
+        };
+        let qualifier = first.checked_sub(1).map(|k| &tokens[k]);
+        object.into_iter().chain(qualifier).find(|t| is_subject(t)).map(|t| t.text.to_string())
     }
 
     /// Builds traceability map linking clauses to artifacts.
+    /// Declarations are identified as `clause_{name}`. Clauses of a numbered section
+    /// are identified as `clause_{number}`, or `clause_{number}_{k}` when the section
+    /// has several; others as `clause_{i}`.
     fn build_traceability_map(
-        clauses: &[String],
+        clauses: &[Clause],
+        sections: &[Section],
+        declarations: &Declarations,
         dio_by_clause: &BTreeMap<usize, Vec<DIOInvariant>>,
         auth_by_clause: &BTreeMap<usize, Vec<ZTAuthority>>,
         cost_by_clause: &BTreeMap<usize, Vec<ICAECostConstraint>>,
+        revocations_by_clause: &BTreeMap<usize, Vec<RevocationRule>>,
     ) -> Vec<TraceabilityEntry> {
         let mut entries = Vec::new();
 
-        for i in 0..clauses.len() {
+        for (i, clause) in clauses.iter().enumerate() {
             let invariant_ids: Vec<String> = dio_by_clause
                 .get(&i)
                 .map(|v| v.iter().map(|inv| inv.id.clone()).collect())
@@ -601,13 +3045,32 @@ impl PolicyCompiler {
                 .map(|v| v.iter().map(|cost| cost.id.clone()).collect())
                 .unwrap_or_default();
 
+            let revocation_ids: Vec<String> = revocations_by_clause
+                .get(&i)
+                .map(|v| v.iter().map(|rule| rule.id.clone()).collect())
+                .unwrap_or_default();
+
+            // Declarations use their name and numbered clauses the author's section number
+            let section = sections.iter().find(|section| section.clause_indices.contains(&i));
+            let clause_id = match (declarations.get(&i), section) {
+                (Some(Ok(declaration)), _) => format!("clause_{}", declaration.name),
+                (_, Some(section)) if section.clause_indices.len() == 1 => format!("clause_{}", section.number),
+                (_, Some(section)) => {
+                    let k = section.clause_indices.iter().position(|index| *index == i).unwrap_or_default();
+                    format!("clause_{}_{}", section.number, k)
+                }
+                (_, None) => format!("clause_{}", i),
+            };
+
             entries.push(TraceabilityEntry {
-                clause_id: format!("clause_{}", i),
+                clause_id,
                 clause_index: i,
-                clause_text: clauses.get(i).cloned().unwrap_or_default(),
+                clause_text: clause.text.clone(),
+                span: clause.span,
                 invariant_ids,
                 authority_ids,
                 cost_ids,
+                revocation_ids,
             });
         }
 
@@ -636,15 +3099,20 @@ impl PolicyCompiler {
         CompilationResult {
             intent_normalization: IntentNormalization {
                 clauses: Vec::new(),
+                sections: Vec::new(),
+                polarities: Vec::new(),
                 assumptions: Vec::new(),
                 exclusions: Vec::new(),
             },
             dio_invariants: Vec::new(),
             zt_authority_graph: Vec::new(),
+            delegation_graph: DelegationGraph::new(),
             icae_constraints: Vec::new(),
+            revocation_rules: Vec::new(),
             traceability_map: Vec::new(),
             verdict: CompilationStatus::Fail,
             errors,
+            #[allow(deprecated)]
             failures,
         }
     }
@@ -665,6 +3133,44 @@ mod unit_tests {
         assert_eq!(Principal::from_clause("SYSTEMWIDE"), None);
     }
 
+    #[test]
+    fn test_principal_registry_resolve() {
+        let registry = PrincipalRegistry::new()
+            .with("billing-service", Principal::Service)
+            .with("ROLE:auditor", Principal::User)
+            .with("Data Platform", Principal::System);
+
+        let resolved = registry.resolve("Invoices must be logged by Billing-Service").unwrap();
+        assert_eq!(resolved.identity, "billing-service");
+        assert_eq!(resolved.kind, Principal::Service);
+
+        assert_eq!(registry.resolve("role:auditor must audit exports").unwrap().identity, "ROLE:auditor");
+        // Multi-word names win over the built-in words they contain
+        assert_eq!(registry.resolve("the data platform SERVICE").unwrap().identity, "Data Platform");
+        // Earliest mention wins
+        assert_eq!(registry.resolve("USER then svc").unwrap().identity, "USER");
+        assert_eq!(registry.resolve("nobody here"), None);
+        assert_eq!(registry.get("BILLING-SERVICE").map(|p| p.kind), Some(Principal::Service));
+    }
+
+    #[test]
+    fn test_authority_role_from_verb() {
+        assert_eq!(AuthorityRole::from_verb("approves"), AuthorityRole::Approver);
+        assert_eq!(AuthorityRole::from_verb("authorized"), AuthorityRole::Approver);
+        assert_eq!(AuthorityRole::from_verb("audited"), AuthorityRole::Auditor);
+        assert_eq!(AuthorityRole::from_verb("reviewing"), AuthorityRole::Auditor);
+        assert_eq!(AuthorityRole::from_verb("log"), AuthorityRole::Actor);
+    }
+
+    #[test]
+    fn test_time_span_in_tokens() {
+        let found = |text: &str| TimeSpan::find_in_tokens(&tokenize(text));
+        assert_eq!(found("for 30 days"), Some(TimeSpan { amount: 30, unit: TimeUnit::Days }));
+        assert_eq!(found("expires after 1 year"), Some(TimeSpan { amount: 1, unit: TimeUnit::Years }));
+        assert_eq!(found("for 2.5 hours"), None);
+        assert_eq!(found("for several weeks"), None);
+    }
+
     #[test]
     fn test_measurement_unit_from_clause() {
         assert_eq!(MeasurementUnit::from_clause("1000 USD"), Some(MeasurementUnit::Usd));
@@ -682,10 +3188,83 @@ mod unit_tests {
             clause_index: 0,
             clause: "should log".to_string(),
             modal_word: "should".to_string(),
+            span: Span { start: 0, end: 6, line: 1, column: 1, end_line: 1, end_column: 7 },
         };
         assert!(err.to_string().contains("modal language"));
     }
 
+    #[test]
+    fn test_parse_clauses_spans() {
+        let input = "  First must log.\nSecond must audit.";
+        let (clauses, ..) = PolicyCompiler::parse_clauses(input);
+        assert_eq!(clauses.len(), 2);
+
+        assert_eq!(clauses[0].text, "First must log");
+        assert_eq!(clauses[0].span.text(input), Some("First must log"));
+        assert_eq!((clauses[0].span.line, clauses[0].span.column), (1, 3));
+
+        assert_eq!(clauses[1].span.text(input), Some("Second must audit"));
+        assert_eq!((clauses[1].span.line, clauses[1].span.column), (2, 1));
+        assert_eq!((clauses[1].span.end_line, clauses[1].span.end_column), (2, 18));
+    }
+
+    #[test]
+    fn test_clause_subspan_across_lines() {
+        let input = "Intro must log.  Data must\nbe audited.";
+        let (clauses, ..) = PolicyCompiler::parse_clauses(input);
+        let clause = &clauses[1];
+        let pos = clause.text.find("audited").unwrap();
+        let span = clause.subspan(pos, pos + "audited".len());
+
+        assert_eq!(span.text(input), Some("audited"));
+        assert_eq!((span.line, span.column), (2, 4));
+        assert_eq!((span.end_line, span.end_column), (2, 11));
+    }
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("1000"), Some(1000.0));
+        assert_eq!(parse_quantity("1,000"), Some(1000.0));
+        assert_eq!(parse_quantity("1,234,567.25"), Some(1_234_567.25));
+        assert_eq!(parse_quantity("99.50"), Some(99.5));
+        assert_eq!(parse_quantity("1k"), Some(1000.0));
+        assert_eq!(parse_quantity("2.5M"), Some(2_500_000.0));
+        assert_eq!(parse_quantity("3B"), Some(3_000_000_000.0));
+        // Malformed separators and non-numbers are rejected
+        assert_eq!(parse_quantity("1,00"), None);
+        assert_eq!(parse_quantity("1000,000"), None);
+        assert_eq!(parse_quantity("1."), None);
+        assert_eq!(parse_quantity("k"), None);
+        assert_eq!(parse_quantity("month"), None);
+    }
+
+    #[test]
+    fn test_extract_ceiling_adjacent_to_unit() {
+        let ceiling = PolicyCompiler::extract_ceiling("Spend must not exceed 2.5M tokens per day", MeasurementUnit::Tokens);
+        assert_eq!(ceiling, Ok(Some(2_500_000.0)));
+
+        let ceiling = PolicyCompiler::extract_ceiling("Budget is capped at USD 99.50 for SERVICE", MeasurementUnit::Usd);
+        assert_eq!(ceiling, Ok(Some(99.5)));
+
+        let ceiling = PolicyCompiler::extract_ceiling("Cost must be tracked in USD", MeasurementUnit::Usd);
+        assert_eq!(ceiling, Ok(None));
+    }
+
+    #[test]
+    fn test_budget_period_mentions() {
+        let periods = |clause: &str| -> Vec<BudgetPeriod> {
+            BudgetPeriod::find_in_clause(clause).into_iter().map(|(p, _)| p).collect()
+        };
+        assert_eq!(periods("1000 USD per month"), vec![BudgetPeriod::Monthly]);
+        assert_eq!(periods("1000 USD/day"), vec![BudgetPeriod::Daily]);
+        assert_eq!(periods("5 USD each request"), vec![BudgetPeriod::PerRequest]);
+        assert_eq!(periods("billed quarterly"), vec![BudgetPeriod::Quarterly]);
+        assert_eq!(periods("10k USD in total"), vec![BudgetPeriod::Lifetime]);
+        assert_eq!(periods("per hour and per week"), vec![BudgetPeriod::Hourly, BudgetPeriod::Weekly]);
+        // Bare unit nouns are not periods
+        assert_eq!(periods("the month of May"), vec![]);
+    }
+
     #[test]
     fn test_truncate_clause_short() {
         let result = PolicyCompiler::truncate_clause("short", 10);
@@ -0,0 +1,253 @@
+//! Markdown front-end for policy documents.
+//!
+//! ATX headings ("## Access") become sections numbered by their nesting ("1",
+//! "1.1", ...). List items, pipe-table rows and prose paragraphs are split into
+//! clauses; table header rows, fenced code, HTML comments, blockquotes and
+//! thematic breaks are commentary and are not compiled.
+//!
+//! Markup is blanked out of a copy of the document that keeps every byte offset,
+//! so clause spans point at the Markdown lines the clauses came from.
+
+use crate::segmenter::segment;
+use crate::{Clause, LineIndex, Section};
+
+/// Fence markers opening and closing code blocks.
+const FENCES: &[&str] = &["```", "~~~"];
+
+/// A run of lines compiled as clauses, such as a paragraph or list item.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    start: usize,
+    end: usize,
+    section: Option<usize>,
+}
+
+/// Parses a Markdown document into clauses and the sections its headings open.
+pub(crate) fn parse(document: &str) -> (Vec<Clause>, Vec<Section>) {
+    let index = LineIndex::new(document);
+    let mut shadow = String::with_capacity(document.len());
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut open: Option<Block> = None;
+    let mut sections: Vec<Section> = Vec::new();
+    let mut children: Vec<usize> = Vec::new();
+    let mut top_level = 0;
+    let mut nesting: Vec<(usize, usize)> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut in_comment = false;
+
+    let lines: Vec<(usize, &str)> = document
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .collect();
+
+    for (k, &(offset, line)) in lines.iter().enumerate() {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let trimmed = content.trim_start();
+        let indent = content.len() - trimmed.len();
+        let current = nesting.last().map(|&(_, section)| section);
+
+        // Commentary and code are blanked and end the open block
+        let commentary = if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            true
+        } else if in_comment {
+            in_comment = !content.contains("-->");
+            true
+        } else if let Some(marker) = FENCES.iter().find(|marker| trimmed.starts_with(**marker)) {
+            fence = Some(*marker);
+            true
+        } else if let Some(comment) = trimmed.strip_prefix("<!--") {
+            in_comment = !comment.contains("-->");
+            true
+        } else {
+            let next = lines.get(k + 1).map(|&(_, next)| next);
+            trimmed.starts_with('>') || is_thematic_break(trimmed) || is_table_header(trimmed, next)
+        };
+        if commentary || trimmed.is_empty() {
+            blocks.extend(open.take());
+            push_blank(&mut shadow, line);
+            continue;
+        }
+
+        if let Some((level, (title_start, title_end))) = heading(trimmed) {
+            blocks.extend(open.take());
+            push_blank(&mut shadow, line);
+            while matches!(nesting.last(), Some(&(open_level, _)) if open_level >= level) {
+                nesting.pop();
+            }
+            let parent = nesting.last().map(|&(_, section)| section);
+            let number = match parent {
+                Some(parent) => {
+                    children[parent] += 1;
+                    format!("{}.{}", sections[parent].number, children[parent])
+                }
+                None => {
+                    top_level += 1;
+                    top_level.to_string()
+                }
+            };
+            sections.push(Section {
+                number,
+                heading: Some(trimmed[title_start..title_end].to_string()),
+                parent: parent.map(|parent| sections[parent].number.clone()),
+                clause_indices: Vec::new(),
+                span: index.span(offset + indent + title_start, offset + indent + title_end),
+            });
+            children.push(0);
+            nesting.push((level, sections.len() - 1));
+            continue;
+        }
+
+        if trimmed.starts_with('|') {
+            // Each table row is a block of its own, with the pipes blanked
+            blocks.extend(open.take());
+            shadow.extend(line.chars().map(|c| if c == '|' { ' ' } else { c }));
+            blocks.push(Block { start: offset + indent, end: offset + content.len(), section: current });
+            continue;
+        }
+
+        let marker = list_marker(trimmed);
+        if marker > 0 {
+            blocks.extend(open.take());
+            push_blank(&mut shadow, &line[..indent + marker]);
+            shadow.push_str(&line[indent + marker..]);
+            open = Some(Block { start: offset + indent + marker, end: offset + content.len(), section: current });
+            continue;
+        }
+
+        // Prose and list item continuation lines
+        shadow.push_str(line);
+        match open.as_mut() {
+            Some(block) => block.end = offset + content.len(),
+            None => open = Some(Block { start: offset + indent, end: offset + content.len(), section: current }),
+        }
+    }
+    blocks.extend(open);
+
+    let mut clauses = Vec::new();
+    for block in blocks {
+        for segment in segment(&shadow[block.start..block.end]) {
+            let (start, end) = (block.start + segment.start, block.start + segment.end);
+            if let Some(section) = block.section {
+                sections[section].clause_indices.push(clauses.len());
+            }
+            clauses.push(Clause { text: shadow[start..end].to_string(), span: index.span(start, end) });
+        }
+    }
+
+    (clauses, sections)
+}
+
+/// Appends `text` to the shadow document as spaces of the same byte length,
+/// keeping line breaks.
+fn push_blank(shadow: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\n' | '\r' => shadow.push(c),
+            _ => shadow.push_str(&" ".repeat(c.len_utf8())),
+        }
+    }
+}
+
+/// Returns the level of an ATX heading and the byte range of its title
+/// ("## Access ##" → 2 and the range of "Access").
+fn heading(line: &str) -> Option<(usize, (usize, usize))> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    if level == 0 || level > 6 || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let title = rest.trim_start();
+    let start = line.len() - title.len();
+    let title = title.trim_end();
+    // A closing sequence of '#' must follow whitespace ("## Access ##", not "C#")
+    let unclosed = title.trim_end_matches('#');
+    let title = if unclosed.is_empty() || unclosed.ends_with([' ', '\t']) { unclosed.trim_end() } else { title };
+    Some((level, (start, start + title.len())))
+}
+
+/// Returns the byte length of the list marker opening `line` and the whitespace
+/// after it ("- ", "* ", "+ ", "1. ", "2) "), or 0 if there is none.
+fn list_marker(line: &str) -> usize {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let marker = match line[digits..].chars().next() {
+        Some('-') | Some('*') | Some('+') if digits == 0 => 1,
+        Some('.') | Some(')') if digits > 0 => digits + 1,
+        _ => return 0,
+    };
+    let rest = &line[marker..];
+    let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    if spaces == 0 {
+        return 0;
+    }
+    marker + spaces
+}
+
+/// Returns true for a thematic break: three or more `-`, `*` or `_`, optionally
+/// separated by spaces.
+fn is_thematic_break(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ['-', '*', '_'].iter().any(|mark| marks.iter().all(|c| c == mark))
+}
+
+/// Returns true for the header row of a pipe table, or its delimiter row
+/// ("|---|:--:|").
+fn is_table_header(line: &str, next: Option<&str>) -> bool {
+    let is_delimiter = |line: &str| {
+        line.starts_with('|') && line.contains('-') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+    };
+    let line = line.trim_end();
+    is_delimiter(line) || (line.starts_with('|') && matches!(next, Some(next) if is_delimiter(next.trim())))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn texts(document: &str) -> Vec<String> {
+        parse(document).0.into_iter().map(|clause| clause.text).collect()
+    }
+
+    #[test]
+    fn test_headings_number_sections() {
+        let document = "# Access\n\nSYSTEM must log access.\n\n### Audit ##\n- USER must audit logs\n## Keys\n- SERVICE must rotate keys\n# Costs\n";
+        let (clauses, sections) = parse(document);
+        let outline: Vec<(&str, &str, Option<&str>)> = sections.iter()
+            .map(|s| (s.number.as_str(), s.heading.as_deref().unwrap(), s.parent.as_deref()))
+            .collect();
+        assert_eq!(outline, vec![
+            ("1", "Access", None),
+            ("1.1", "Audit", Some("1")),
+            ("1.2", "Keys", Some("1")),
+            ("2", "Costs", None),
+        ]);
+        assert_eq!(sections[0].clause_indices, vec![0]);
+        assert_eq!(sections[2].clause_indices, vec![2]);
+        assert_eq!((clauses[1].span.line, clauses[1].span.column), (6, 3));
+        assert_eq!(sections[1].span.text(document), Some("Audit"));
+    }
+
+    #[test]
+    fn test_commentary_is_skipped() {
+        let document = "SYSTEM must log access.\n```\nUSER must delete logs.\n```\n<!-- USER must\nskip this -->\n> Note: SERVICE must ignore this.\n---\n| Principal | Rule |\n|---|---|\n| SYSTEM | must encrypt data |\n";
+        assert_eq!(texts(document), vec!["SYSTEM must log access", "SYSTEM   must encrypt data"]);
+    }
+
+    #[test]
+    fn test_list_items_and_paragraphs() {
+        let document = "1. SYSTEM must log\n   access.\n2) USER must audit logs. SERVICE must rotate keys\n* SYSTEM must back up data\n\nSYSTEM must encrypt\nbackups";
+        assert_eq!(texts(document), vec![
+            "SYSTEM must log\n   access",
+            "USER must audit logs",
+            "SERVICE must rotate keys",
+            "SYSTEM must back up data",
+            "SYSTEM must encrypt\nbackups",
+        ]);
+    }
+}
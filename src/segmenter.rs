@@ -0,0 +1,238 @@
+//! Splitting policy text into clauses.
+//!
+//! A clause ends at a sentence-ending period, a semicolon, a blank line or the
+//! start of a list item. Periods inside numbers ("99.50"), abbreviations
+//! ("e.g.") and quoted strings (`"audit.log"`) do not end a clause, and list
+//! markers ("-", "*", "1.", "2)", "(a)") are not part of the clause text.
+//!
+//! Outline numbers ("1.", "1.2", "1.2.3") number the item they open; every clause
+//! of the item, up to the next list item or blank line, carries the number.
+
+/// Abbreviations whose period never ends a clause.
+const ABBREVIATIONS: &[&str] = &["e.g", "i.e", "vs", "cf", "viz", "approx", "incl", "excl", "mr", "mrs", "dr"];
+
+/// Abbreviations whose period ends a clause only when a capitalized word follows.
+const FINAL_ABBREVIATIONS: &[&str] = &["etc"];
+
+/// Returns true if `word`, lowercased and without its final period, is an
+/// abbreviation ("e.g", "etc").
+pub(crate) fn is_abbreviation(word: &str) -> bool {
+    ABBREVIATIONS.contains(&word) || FINAL_ABBREVIATIONS.contains(&word)
+}
+/// Opening quote characters and the characters that close them.
+const QUOTES: &[(char, char)] = &[('"', '"'), ('\u{201c}', '\u{201d}'), ('`', '`')];
+
+/// A clause of the policy text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Segment {
+    /// Byte range of the clause, trimmed of surrounding whitespace.
+    pub start: usize,
+    pub end: usize,
+    /// Byte range of the outline number ("1.2") of the item the clause belongs to.
+    pub number: Option<(usize, usize)>,
+}
+
+/// Splits `text` into clauses; empty clauses are dropped.
+pub(crate) fn segment(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut number = None;
+    let mut quote: Option<char> = None;
+    let mut line_start = true;
+    let mut pos = 0;
+
+    while let Some(c) = text[pos..].chars().next() {
+        if line_start {
+            line_start = false;
+            let line = &text[pos..];
+            let rest = line.trim_start_matches([' ', '\t']);
+            let blank = rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n");
+            let marker = list_marker(rest);
+            if blank || marker.is_some() {
+                push(&mut segments, text, start, pos, number);
+                let indent = pos + line.len() - rest.len();
+                number = marker.and_then(|(_, label)| label).map(|label| (indent, indent + label));
+                pos = indent + marker.map_or(0, |(len, _)| len);
+                start = pos;
+                continue;
+            }
+        }
+
+        let next = pos + c.len_utf8();
+        match quote {
+            Some(close) if c == close => quote = None,
+            Some(_) => {}
+            None => match c {
+                ';' => {
+                    push(&mut segments, text, start, pos, number);
+                    start = next;
+                }
+                '.' if ends_clause(text, pos) => {
+                    push(&mut segments, text, start, pos, number);
+                    start = next;
+                }
+                _ => {
+                    // Quotes close on the same line; an unmatched quote is literal
+                    if let Some(&(_, close)) = QUOTES.iter().find(|(open, _)| *open == c) {
+                        let line_end = text[next..].find('\n').map_or(text.len(), |end| next + end);
+                        if text[next..line_end].contains(close) {
+                            quote = Some(close);
+                        }
+                    }
+                }
+            },
+        }
+        if c == '\n' {
+            line_start = true;
+            quote = None;
+        }
+        pos = next;
+    }
+
+    push(&mut segments, text, start, text.len(), number);
+    segments
+}
+
+/// Records `text[start..end]`, trimmed, unless it is empty.
+fn push(segments: &mut Vec<Segment>, text: &str, start: usize, end: usize, number: Option<(usize, usize)>) {
+    let segment = &text[start..end];
+    let trimmed = segment.trim();
+    if !trimmed.is_empty() {
+        let start = start + (segment.len() - segment.trim_start().len());
+        segments.push(Segment { start, end: start + trimmed.len(), number });
+    }
+}
+
+/// Returns true if the period at byte `pos` ends a clause.
+fn ends_clause(text: &str, pos: usize) -> bool {
+    let after = &text[pos + 1..];
+    // "99.50"
+    if text[..pos].ends_with(|c: char| c.is_ascii_digit()) && after.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+
+    // The whole word around the period, so both periods of "e.g." find "e.g"
+    let word_start = text[..pos].char_indices().rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let word_end = pos + 1 + after.find(char::is_whitespace).unwrap_or(after.len());
+    let word = text[word_start..word_end].trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    if ABBREVIATIONS.contains(&word.as_str()) {
+        return false;
+    }
+    if FINAL_ABBREVIATIONS.contains(&word.as_str()) {
+        return !matches!(after.trim_start().chars().next(), Some(c) if !c.is_uppercase());
+    }
+    true
+}
+
+/// Returns the byte length of the list marker opening `line`, including the
+/// whitespace after it, and the length of its outline number if it has one. A
+/// marker is a bullet ("-", "*", "+", "•"), an outline number ("1.", "1.2",
+/// "1.2."), a number ("2)", "(3)") or a letter ("a)", "(b)").
+fn list_marker(line: &str) -> Option<(usize, Option<usize>)> {
+    let marker_end = line.find([' ', '\t'])?;
+    let marker = &line[..marker_end];
+    let rest = &line[marker_end..];
+    let len = marker_end + (rest.len() - rest.trim_start_matches([' ', '\t']).len());
+
+    let outline = marker.strip_suffix('.').unwrap_or(marker);
+    let levels: Vec<&str> = outline.split('.').collect();
+    if levels.iter().all(|level| !level.is_empty() && level.chars().all(|c| c.is_ascii_digit()))
+        && (levels.len() > 1 || outline.len() < marker.len())
+    {
+        return Some((len, Some(outline.len())));
+    }
+
+    let label = match marker {
+        "-" | "*" | "+" | "\u{2022}" => "",
+        _ => marker.strip_prefix('(').and_then(|m| m.strip_suffix(')')).or_else(|| marker.strip_suffix(')'))?,
+    };
+    let numbered = !label.is_empty() && label.chars().all(|c| c.is_ascii_digit());
+    let lettered = label.len() == 1 && label.chars().all(|c| c.is_ascii_lowercase());
+    if !label.is_empty() && !numbered && !lettered {
+        return None;
+    }
+    Some((len, None))
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    fn clauses(text: &str) -> Vec<&str> {
+        segment(text).into_iter().map(|segment| &text[segment.start..segment.end]).collect()
+    }
+
+    #[test]
+    fn test_periods_inside_numbers_names_and_abbreviations() {
+        assert_eq!(clauses("Cost must not exceed 99.50 USD by SERVICE. SYSTEM must log access."), vec![
+            "Cost must not exceed 99.50 USD by SERVICE",
+            "SYSTEM must log access",
+        ]);
+        assert_eq!(clauses("SYSTEM must log \"audit.log\", e.g. access events, i.e. reads."), vec![
+            "SYSTEM must log \"audit.log\", e.g. access events, i.e. reads",
+        ]);
+        assert_eq!(clauses("SYSTEM must log reads, writes etc. USER must audit logs etc. daily."), vec![
+            "SYSTEM must log reads, writes etc",
+            "USER must audit logs etc. daily",
+        ]);
+        // Whitespace wider than one byte before a period
+        assert_eq!(clauses("SYSTEM must log foo\u{a0}bar. USER must read e.g.\u{2003}reports."), vec![
+            "SYSTEM must log foo\u{a0}bar",
+            "USER must read e.g.\u{2003}reports",
+        ]);
+    }
+
+    #[test]
+    fn test_periods_that_still_end_a_clause() {
+        assert_eq!(clauses("Requests must complete within 200 ms. USER must audit logs."), vec![
+            "Requests must complete within 200 ms",
+            "USER must audit logs",
+        ]);
+        assert_eq!(clauses("USER must log.SYSTEM must audit records."), vec![
+            "USER must log",
+            "SYSTEM must audit records",
+        ]);
+    }
+
+    #[test]
+    fn test_quotes_and_semicolons() {
+        assert_eq!(clauses("SYSTEM must log \"access. denied\"; USER must audit logs."), vec![
+            "SYSTEM must log \"access. denied\"",
+            "USER must audit logs",
+        ]);
+        // An unmatched quote does not swallow the rest of the policy
+        assert_eq!(clauses("SYSTEM must log \"access. USER must audit logs."), vec![
+            "SYSTEM must log \"access",
+            "USER must audit logs",
+        ]);
+    }
+
+    #[test]
+    fn test_list_items_and_blank_lines() {
+        let text = "Rules\n- SYSTEM must log access\n* USER must audit logs\n1. SERVICE must encrypt data\n2) SYSTEM must\n   rotate keys\n(a) USER must approve changes\n\nSYSTEM must back up data";
+        assert_eq!(clauses(text), vec![
+            "Rules",
+            "SYSTEM must log access",
+            "USER must audit logs",
+            "SERVICE must encrypt data",
+            "SYSTEM must\n   rotate keys",
+            "USER must approve changes",
+            "SYSTEM must back up data",
+        ]);
+        assert_eq!(list_marker("-5 USD"), None);
+        assert_eq!(list_marker("A. Smith"), None);
+        assert_eq!(list_marker("1.2  USER"), Some((5, Some(3))));
+    }
+
+    #[test]
+    fn test_outline_numbers() {
+        let text = "1. Access\n1.1 USER must log access. SYSTEM must audit logs.\n  1.2.  SERVICE must encrypt data\n- SYSTEM must rotate keys";
+        let numbers: Vec<Option<&str>> = segment(text)
+            .into_iter()
+            .map(|segment| segment.number.map(|(start, end)| &text[start..end]))
+            .collect();
+        assert_eq!(numbers, vec![Some("1"), Some("1.1"), Some("1.1"), Some("1.2"), None]);
+    }
+}
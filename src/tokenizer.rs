@@ -0,0 +1,166 @@
+//! Word tokenizer shared by every compilation pass.
+//!
+//! Vocabulary is matched on word and phrase boundaries rather than by substring,
+//! so "mayor" does not contain the modal "may", "logistics" is not the verb "log"
+//! and "Europe" is not the unit EUR.
+
+/// Inflectional endings accepted when matching a vocabulary term against a word.
+const INFLECTION_SUFFIXES: &[&str] = &["s", "es", "d", "ed", "ing"];
+
+/// A single word of policy text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    /// Byte offset of the word within the tokenized text.
+    pub offset: usize,
+    /// The word as written, without surrounding punctuation.
+    pub text: &'a str,
+    /// The word in lowercase, used for vocabulary matching.
+    pub lower: String,
+}
+
+impl Token<'_> {
+    /// Returns the byte offset just past the end of the word.
+    pub fn end(&self) -> usize {
+        self.offset + self.text.len()
+    }
+}
+
+/// Splits text into words with their byte offsets, trimming surrounding punctuation.
+///
+/// Words are separated by whitespace and slashes, so "USD/month" yields "USD" and
+/// "month". Inner punctuation is kept: "billing-service", "svc:ingest" and "2.5M"
+/// are single words.
+pub(crate) fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_whitespace() || c == '/', start) {
+            (false, None) => start = Some(i),
+            (true, Some(begin)) => {
+                let raw = &text[begin..i];
+                let trimmed = raw.trim_start_matches(|c: char| !c.is_alphanumeric());
+                let lead = raw.len() - trimmed.len();
+                let trimmed = trimmed.trim_end_matches(|c: char| !c.is_alphanumeric());
+                if !trimmed.is_empty() {
+                    tokens.push(Token {
+                        offset: begin + lead,
+                        text: trimmed,
+                        lower: trimmed.to_lowercase(),
+                    });
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+/// Finds `phrase` as an exact run of whole words. Returns the byte range covered.
+pub(crate) fn find_phrase(tokens: &[Token<'_>], phrase: &str) -> Option<(usize, usize)> {
+    find_words(tokens, phrase, |word, part, _| word == part)
+}
+
+/// Finds `term` as a run of whole words whose final word may be inflected
+/// ("log" matches "logged", "cost" matches "costs"). Returns the byte range covered.
+pub(crate) fn find_term(tokens: &[Token<'_>], term: &str) -> Option<(usize, usize)> {
+    find_words(tokens, term, |word, part, is_last| {
+        if is_last { is_inflection_of(word, part) } else { word == part }
+    })
+}
+
+/// Returns true if any word in `tokens` is one of `words` exactly.
+pub(crate) fn contains_word(tokens: &[Token<'_>], words: &[&str]) -> bool {
+    tokens.iter().any(|token| words.contains(&token.lower.as_str()))
+}
+
+fn find_words<F>(tokens: &[Token<'_>], phrase: &str, matches: F) -> Option<(usize, usize)>
+where
+    F: Fn(&str, &str, bool) -> bool,
+{
+    let parts: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+    if parts.is_empty() || parts.len() > tokens.len() {
+        return None;
+    }
+    let last = parts.len() - 1;
+    for window in tokens.windows(parts.len()) {
+        let matched = window.iter().zip(&parts).enumerate()
+            .all(|(k, (token, part))| matches(&token.lower, part, k == last));
+        if matched {
+            return Some((window[0].offset, window[last].end()));
+        }
+    }
+    None
+}
+
+/// Returns true if `word` is `base` or a regular inflection of it
+/// ("logs", "logged", "capping", "denied").
+pub(crate) fn is_inflection_of(word: &str, base: &str) -> bool {
+    if let Some(rest) = word.strip_prefix(base) {
+        if rest.is_empty() || INFLECTION_SUFFIXES.contains(&rest) {
+            return true;
+        }
+        // Doubled final consonant: "log" -> "logged", "cap" -> "capping"
+        if let Some(last) = base.chars().last() {
+            if let Some(suffix) = rest.strip_prefix(last) {
+                return !"aeiou".contains(last) && (suffix == "ed" || suffix == "ing");
+            }
+        }
+        return false;
+    }
+    // Silent final "e" drops before "-ing": "enforce" -> "enforcing"
+    if let Some(stem) = base.strip_suffix('e') {
+        if word.strip_prefix(stem) == Some("ing") {
+            return true;
+        }
+    }
+    // Final "y" becomes "i": "deny" -> "denied", "denies"
+    match base.strip_suffix('y') {
+        Some(stem) => word.strip_prefix(stem).map(|rest| rest == "ied" || rest == "ies").unwrap_or(false),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_offsets_and_punctuation() {
+        let text = "Cost (USD/month) of \"billing-service\".";
+        let tokens = tokenize(text);
+        let words: Vec<&str> = tokens.iter().map(|t| t.text).collect();
+
+        assert_eq!(words, vec!["Cost", "USD", "month", "of", "billing-service"]);
+        assert_eq!(&text[tokens[1].offset..tokens[1].end()], "USD");
+        assert_eq!(tokens[4].lower, "billing-service");
+    }
+
+    #[test]
+    fn test_find_phrase_on_word_boundaries() {
+        let tokens = tokenize("The mayor acts where reasonable");
+        assert_eq!(find_phrase(&tokens, "may"), None);
+        assert_eq!(find_phrase(&tokens, "where reasonable"), Some((15, 31)));
+    }
+
+    #[test]
+    fn test_find_term_accepts_inflections_only() {
+        let tokens = tokenize("Logistics data is logged");
+        assert_eq!(find_term(&tokens, "log"), Some((18, 24)));
+        assert_eq!(find_term(&tokenize("logistics"), "log"), None);
+    }
+
+    #[test]
+    fn test_is_inflection_of() {
+        assert!(is_inflection_of("logs", "log"));
+        assert!(is_inflection_of("logging", "log"));
+        assert!(is_inflection_of("denied", "deny"));
+        assert!(is_inflection_of("enforcing", "enforce"));
+        assert!(is_inflection_of("required", "require"));
+        assert!(!is_inflection_of("logistics", "log"));
+        assert!(!is_inflection_of("records", "recordkeeping"));
+        assert!(!is_inflection_of("allowance", "allow"));
+    }
+}
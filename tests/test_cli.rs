@@ -0,0 +1,195 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Creates an empty scratch directory unique to this test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("poc-cli-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create scratch dir");
+    dir
+}
+
+fn poc(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_poc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn poc");
+    if let Some(input) = stdin {
+        // poc may exit before reading stdin; the exit code is what's tested
+        let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    }
+    child.wait_with_output().expect("wait for poc")
+}
+
+// =============================================================================
+// Compile Command Tests
+// =============================================================================
+
+#[test]
+fn test_compile_file_passes_and_writes_artifacts() {
+    let dir = scratch_dir("pass");
+    let policy = dir.join("policy.txt");
+    fs::write(&policy, "All actions must be logged by SYSTEM. Cost of logging cannot exceed 1000 USD per month by SERVICE.").unwrap();
+    let out_dir = dir.join("out");
+
+    let output = poc(&["compile", policy.to_str().unwrap(), "--out-dir", out_dir.to_str().unwrap()], None);
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Compilation Result: PASS"));
+
+    for file in ["result.json", "dio_invariants.json", "zt_authority_graph.json", "delegation_graph.json", "icae_constraints.json", "revocation_rules.json", "traceability_map.json"] {
+        assert!(out_dir.join(file).is_file(), "missing {}", file);
+    }
+    let dio: serde_json::Value = serde_json::from_str(&fs::read_to_string(out_dir.join("dio_invariants.json")).unwrap()).unwrap();
+    assert_eq!(dio[0]["id"], "dio_0");
+}
+
+#[test]
+fn test_compile_stdin_failure_exits_non_zero() {
+    let dir = scratch_dir("fail");
+    let out_dir = dir.join("out");
+
+    let output = poc(&["compile", "-", "--out-dir", out_dir.to_str().unwrap()], Some("Actions should be logged by SYSTEM."));
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Compilation Result: FAIL"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<stdin>:1:9:"), "stderr: {}", stderr);
+    assert!(stderr.contains("modal language"));
+
+    let result: serde_json::Value = serde_json::from_str(&fs::read_to_string(out_dir.join("result.json")).unwrap()).unwrap();
+    assert_eq!(result["verdict"], "FAIL");
+}
+
+#[test]
+fn test_compile_report_all_flag() {
+    let dir = scratch_dir("report-all");
+    let out_dir = dir.join("out");
+
+    let output = poc(
+        &["compile", "-", "--report-all", "--out-dir", out_dir.to_str().unwrap()],
+        Some("Records must be audited. Actions should be logged by SYSTEM."),
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("missing explicit principal"));
+    assert!(stderr.contains("modal language"));
+}
+
+#[test]
+fn test_compile_with_declared_principal() {
+    let dir = scratch_dir("principal");
+    let out_dir = dir.join("out");
+    let policy = "Invoices must be logged by billing-service.";
+
+    let failed = poc(&["compile", "-", "--out-dir", out_dir.to_str().unwrap()], Some(policy));
+    assert_eq!(failed.status.code(), Some(1));
+
+    let output = poc(
+        &["compile", "-", "--principal", "billing-service=service", "--out-dir", out_dir.to_str().unwrap()],
+        Some(policy),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let zt: serde_json::Value = serde_json::from_str(&fs::read_to_string(out_dir.join("zt_authority_graph.json")).unwrap()).unwrap();
+    assert_eq!(zt[0]["identity"], "billing-service");
+    assert_eq!(zt[0]["principal"], "SERVICE");
+
+    assert_eq!(poc(&["compile", "-", "--principal", "billing-service=robot"], Some(policy)).status.code(), Some(2));
+}
+
+#[test]
+fn test_compile_markdown_file() {
+    let dir = scratch_dir("markdown");
+    let policy = dir.join("policy.md");
+    fs::write(&policy, "# Logging\n\n- All actions must be logged by SYSTEM\n- Records should be audited by USER\n").unwrap();
+    let out_dir = dir.join("out");
+
+    let output = poc(&["compile", policy.to_str().unwrap(), "--out-dir", out_dir.to_str().unwrap()], None);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("policy.md:4:11:"), "stderr: {}", stderr);
+
+    let stdin = poc(&["compile", "-", "--markdown", "--out-dir", out_dir.to_str().unwrap()], Some("# Logging\n- All actions must be logged by SYSTEM\n"));
+    assert_eq!(stdin.status.code(), Some(0));
+    let trace: serde_json::Value = serde_json::from_str(&fs::read_to_string(out_dir.join("traceability_map.json")).unwrap()).unwrap();
+    assert_eq!(trace[0]["clause_id"], "clause_1");
+    assert_eq!(trace[0]["span"]["line"], 2);
+}
+
+// =============================================================================
+// Evaluate Command Tests
+// =============================================================================
+
+#[test]
+fn test_evaluate_reports_violations_from_event_stream() {
+    let dir = scratch_dir("evaluate");
+    let out_dir = dir.join("out");
+    let result = out_dir.join("result.json");
+
+    poc(&["compile", "-", "--out-dir", out_dir.to_str().unwrap()], Some("All actions must be logged by SYSTEM."));
+    let clean = "{\"type\": \"execution\", \"action\": \"log\", \"resource\": \"actions\"}\n";
+    let output = poc(&["evaluate", result.to_str().unwrap(), "-"], Some(clean));
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Violations: 0"));
+
+    let policy = dir.join("policy.txt");
+    fs::write(&policy, "All actions must be logged by SYSTEM. Cost of logging cannot exceed 1000 USD per month by SERVICE.").unwrap();
+    poc(&["compile", policy.to_str().unwrap(), "--out-dir", out_dir.to_str().unwrap()], None);
+    let events = dir.join("events.jsonl");
+    fs::write(&events, "{\"type\": \"cost\", \"subject\": \"logging\", \"amount\": 1200, \"unit\": \"USD\"}\n").unwrap();
+    let output = poc(&["evaluate", result.to_str().unwrap(), events.to_str().unwrap()], None);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("VIOLATION_ICAE_1 icae_1 (clause_1)"), "{}", stdout);
+    assert!(stdout.contains("VIOLATION_DIO_0 dio_0 (clause_0)"), "{}", stdout);
+}
+
+#[test]
+fn test_evaluate_rejects_malformed_events() {
+    let dir = scratch_dir("evaluate-bad");
+    let out_dir = dir.join("out");
+    poc(&["compile", "-", "--out-dir", out_dir.to_str().unwrap()], Some("All actions must be logged by SYSTEM."));
+
+    let output = poc(&["evaluate", out_dir.join("result.json").to_str().unwrap(), "-"], Some("\n{\"type\": \"teleport\"}\n"));
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("<stdin>:2: invalid event"));
+}
+
+// =============================================================================
+// Usage Error Tests
+// =============================================================================
+
+#[test]
+fn test_usage_errors_exit_with_two() {
+    assert_eq!(poc(&[], None).status.code(), Some(2));
+    assert_eq!(poc(&["frobnicate"], None).status.code(), Some(2));
+    assert_eq!(poc(&["compile"], None).status.code(), Some(2));
+    assert_eq!(poc(&["compile", "-", "--bogus"], None).status.code(), Some(2));
+}
+
+#[test]
+fn test_missing_input_file_exits_with_two() {
+    let dir = scratch_dir("missing");
+    let output = poc(&["compile", dir.join("nope.txt").to_str().unwrap()], None);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed to read"));
+}
+
+#[test]
+fn test_help_succeeds() {
+    let output = poc(&["--help"], None);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("poc compile"));
+}
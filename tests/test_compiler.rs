@@ -1,6 +1,10 @@
 use poc::{
-    PolicyCompiler, CompilationStatus, CompilationError,
-    Principal, MeasurementUnit,
+    PolicyCompiler, CompilationResult, CompilationStatus, CompilationError, DiagnosticMode,
+    Principal, PrincipalRegistry, MeasurementUnit, BudgetPeriod, AccessEffect, AuthorityRole,
+    TimeSpan, TimeUnit, RevocationTrigger, AuthorityScope,
+    EventPattern, ExecutionEvent, InvariantPredicate, PrecedenceEdge,
+    Evaluator, PolicyEvent, AuthorizationEvent, CostEvent, ArtifactKind,
+    CostLedger, UsageRecord, AlertLevel, BudgetWindow, Guard, Polarity, ExclusionTarget,
 };
 use std::thread;
 
@@ -78,6 +82,200 @@ fn test_modal_where_reasonable_fails() {
     ));
 }
 
+// =============================================================================
+// Source Span Tests
+// =============================================================================
+
+#[test]
+fn test_modal_language_span_points_at_word() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM.\nActions should be audited by USER.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    let span = result.errors[0].span().expect("modal error carries a span");
+    assert_eq!(span.text(policy), Some("should"));
+    assert_eq!((span.line, span.column), (2, 9));
+    assert_eq!(result.errors[0].clause_index(), Some(1));
+}
+
+#[test]
+fn test_missing_principal_span_covers_clause() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM.   Records must be audited.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::MissingPrincipal { clause_index, span, .. } => {
+            assert_eq!(*clause_index, 1);
+            assert_eq!(span.text(policy), Some("Records must be audited"));
+            assert_eq!((span.line, span.column), (1, 41));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_ambiguous_multi_action_span_points_at_conjunction() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Log all actions and audit them.";
+    let result = compiler.compile(policy);
+
+    let span = result.errors[0].span().expect("multi-action error carries a span");
+    assert_eq!(span.text(policy), Some("and"));
+}
+
+#[test]
+fn test_clause_and_traceability_spans() {
+    let compiler = PolicyCompiler::new();
+    let policy = "\n  All actions must be logged by SYSTEM.\n  No unauthorized access allowed by USER.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let clauses = &result.intent_normalization.clauses;
+    assert_eq!(clauses[0].span.text(policy), Some(clauses[0].text.as_str()));
+    assert_eq!((clauses[0].span.line, clauses[0].span.column), (2, 3));
+    assert_eq!((clauses[1].span.line, clauses[1].span.column), (3, 3));
+    assert_eq!(result.traceability_map[1].span, clauses[1].span);
+}
+
+#[test]
+fn test_input_level_errors_have_no_span() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("");
+
+    assert_eq!(result.errors[0].span(), None);
+    assert_eq!(result.errors[0].clause_index(), None);
+}
+
+// =============================================================================
+// Clause Segmentation Tests
+// =============================================================================
+
+#[test]
+fn test_decimal_amount_stays_in_one_clause() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost must not exceed 99.50 USD per month by SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.intent_normalization.clauses.len(), 1);
+    assert_eq!(result.icae_constraints[0].ceiling, Some(99.5));
+}
+
+#[test]
+fn test_abbreviations_and_quoted_names_stay_in_one_clause() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Access to \"audit.log\" must be logged by SYSTEM, e.g. reads. Records must be audited by USER, i.e. daily.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let clauses: Vec<&str> = result.intent_normalization.clauses.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(clauses, vec![
+        "Access to \"audit.log\" must be logged by SYSTEM, e.g. reads",
+        "Records must be audited by USER, i.e. daily",
+    ]);
+}
+
+#[test]
+fn test_non_ascii_whitespace_before_period() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SYSTEM must log foo\u{a0}bar. USER must audit reports.";
+    for result in [compiler.compile(policy), compiler.compile_markdown(policy)] {
+        assert_eq!(result.verdict, CompilationStatus::Pass, "errors: {:?}", result.errors);
+        let clauses: Vec<&str> = result.intent_normalization.clauses.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(clauses, vec!["SYSTEM must log foo\u{a0}bar", "USER must audit reports"]);
+    }
+}
+
+#[test]
+fn test_semicolons_separate_clauses() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("All actions must be logged by SYSTEM; records must be audited by USER");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.traceability_map.len(), 2);
+    assert_eq!(result.traceability_map[1].clause_text, "records must be audited by USER");
+}
+
+#[test]
+fn test_list_items_are_clauses() {
+    let compiler = PolicyCompiler::new();
+    let policy = "- All actions must be logged by SYSTEM\n* Records must be audited by USER\n1. Keys must be rotated by SERVICE\n2) Backups must be encrypted by SYSTEM";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let clauses = &result.intent_normalization.clauses;
+    assert_eq!(clauses.len(), 4);
+    assert_eq!(clauses[2].text, "Keys must be rotated by SERVICE");
+    assert_eq!(clauses[2].span.text(policy), Some("Keys must be rotated by SERVICE"));
+    assert_eq!((clauses[2].span.line, clauses[2].span.column), (3, 4));
+}
+
+// =============================================================================
+// Word Boundary Tests
+// =============================================================================
+
+#[test]
+fn test_modal_word_inside_other_word_passes() {
+    let compiler = PolicyCompiler::new();
+    // "mayor" contains "may", "shoulder" contains "should"
+    let result = compiler.compile("Mayor requests must be logged by SYSTEM. Shoulder records must be audited by USER.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+}
+
+#[test]
+fn test_action_verb_inside_other_word_fails() {
+    let compiler = PolicyCompiler::new();
+    // "logistics" contains "log", "allowance" contains "allow"
+    let result = compiler.compile("Logistics allowance data is kept by SYSTEM.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::MissingActionVerb { .. }
+    ));
+}
+
+#[test]
+fn test_unit_inside_other_word_is_not_detected() {
+    assert_eq!(MeasurementUnit::from_clause("Spend in Europe"), None);
+    assert_eq!(MeasurementUnit::from_clause("Spend in EUR"), Some(MeasurementUnit::Eur));
+
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of Europe storage must be tracked by SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::MissingMeasurementUnit { .. }
+    ));
+}
+
+#[test]
+fn test_cost_indicator_inside_other_word_is_ignored() {
+    let compiler = PolicyCompiler::new();
+    // "costume" contains "cost"
+    let result = compiler.compile("Costume inventory must be recorded by SYSTEM.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert!(result.icae_constraints.is_empty());
+}
+
+#[test]
+fn test_conjunction_and_ordering_words_match_whole_words() {
+    let compiler = PolicyCompiler::new();
+    // "android" contains "and"; "thence" and "aftermath" are not ordering words
+    assert_eq!(compiler.compile("Android events must be logged by SYSTEM.").verdict, CompilationStatus::Pass);
+
+    let result = compiler.compile("Log aftermath and thence audit it by SYSTEM.");
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::AmbiguousMultiAction { .. }
+    ));
+}
+
 // =============================================================================
 // Action Verb Validation Tests
 // =============================================================================
@@ -185,8 +383,88 @@ fn test_assumptions_and_exclusions_parsed() {
     assert_eq!(result.verdict, CompilationStatus::Pass);
     assert_eq!(result.intent_normalization.assumptions.len(), 1);
     assert_eq!(result.intent_normalization.exclusions.len(), 1);
-    assert!(result.intent_normalization.assumptions[0].contains("Assumes"));
-    assert!(result.intent_normalization.exclusions[0].contains("Except"));
+    let assumption = &result.intent_normalization.assumptions[0];
+    assert_eq!((assumption.clause_index, assumption.statement.as_str()), (0, "network must be secure by SYSTEM"));
+    let exclusion = &result.intent_normalization.exclusions[0];
+    assert_eq!(exclusion.clause_index, 2);
+    assert_eq!(exclusion.target, ExclusionTarget::Resource { name: "testing".to_string() });
+    assert_eq!(exclusion.span.text(policy), Some("testing"));
+}
+
+#[test]
+fn test_leading_exclusion_before_comma() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM. Except for testing, USER must audit reports.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let exclusion = &result.intent_normalization.exclusions[0];
+    assert_eq!(exclusion.clause_index, 1);
+    assert_eq!(exclusion.target, ExclusionTarget::Resource { name: "testing".to_string() });
+    assert_eq!(exclusion.span.text(policy), Some("testing"));
+    assert_eq!(result.zt_authority_graph[1].scope.to_string(), "audit:reports");
+}
+
+#[test]
+fn test_excluded_principal_gets_no_authority() {
+    let compiler = PolicyCompiler::builder().principal("ROLE:auditor", Principal::User).build();
+    let result = compiler.compile("SYSTEM must never audit payroll except ROLE:auditor and SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let excluded: Vec<&ExclusionTarget> = result.intent_normalization.exclusions.iter().map(|e| &e.target).collect();
+    assert_eq!(excluded, vec![
+        &ExclusionTarget::Principal { identity: "ROLE:auditor".to_string() },
+        &ExclusionTarget::Principal { identity: "SERVICE".to_string() },
+    ]);
+    let identities: Vec<&str> = result.zt_authority_graph.iter().map(|a| a.identity.as_str()).collect();
+    assert_eq!(identities, vec!["SYSTEM"]);
+
+    // The invariant ignores audits by excluded principals
+    let predicate = &result.dio_invariants[0].predicate;
+    assert!(predicate.evaluate(&[ExecutionEvent::new("audit", "payroll").with_principal("ROLE:auditor")]));
+    assert!(!predicate.evaluate(&[ExecutionEvent::new("audit", "payroll").with_principal("SYSTEM")]));
+}
+
+#[test]
+fn test_excluded_resources_and_conditions() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All requests must be logged by SYSTEM except health checks. SYSTEM must log access unless maintenance is active.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    // A health check is a logged request, but does not count towards the obligation
+    let predicate = &result.dio_invariants[0].predicate;
+    let health_check = ExecutionEvent::new("log", "requests").with_field("kind", "health_checks");
+    assert!(!predicate.evaluate(std::slice::from_ref(&health_check)));
+    assert!(predicate.evaluate(&[health_check, ExecutionEvent::new("log", "requests")]));
+
+    let condition = &result.intent_normalization.exclusions[1];
+    assert_eq!(condition.clause_index, 1);
+    assert_eq!(condition.target, ExclusionTarget::Condition { guard: Guard::Condition { name: "maintenance_active".to_string() } });
+    assert_eq!(condition.span.text(policy), Some("unless maintenance is active"));
+}
+
+#[test]
+fn test_excluded_resource_guards_cost_constraint() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of inference must not exceed 100 USD per month by SERVICE except for testing.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass, "errors: {:?}", result.errors);
+    let constraint = &result.icae_constraints[0];
+    assert_eq!(constraint.subject, "inference");
+    assert_eq!(constraint.guard.as_ref().map(|g| g.to_string()).as_deref(), Some("!testing"));
+
+    let event = PolicyEvent::Cost(CostEvent {
+        subject: "inference".to_string(),
+        amount: 150.0,
+        unit: MeasurementUnit::Usd,
+        timestamp: None,
+    });
+    let cost_violations = |mut evaluator: Evaluator<'_>| {
+        evaluator.observe(&event).iter().filter(|v| v.kind == ArtifactKind::Icae).count()
+    };
+    assert_eq!(cost_violations(Evaluator::new(&result)), 1);
+    assert_eq!(cost_violations(Evaluator::new(&result).with_conditions(["testing"])), 0);
 }
 
 // =============================================================================
@@ -208,6 +486,125 @@ fn test_cost_clause_compiles() {
     assert_eq!(cost.clause_index, 1);
 }
 
+#[test]
+fn test_cost_subject_extracted() {
+    let compiler = PolicyCompiler::new();
+
+    let test_cases = vec![
+        ("Cost must not exceed 1000 USD per month by SERVICE.", "SERVICE"),
+        ("Spend must not exceed 2.5M tokens per day by SERVICE.", "SERVICE"),
+        ("Usage must not exceed 2M tokens/month by SERVICE.", "SERVICE"),
+        ("Cost of api must not exceed 1000 USD per month by SERVICE.", "api"),
+        ("Cost of the inference cluster cannot exceed 100 USD monthly by SERVICE.", "inference"),
+        ("Storage spend must not exceed 200 EUR per day by SERVICE.", "Storage"),
+        ("Token usage of 5000 tokens must be tracked by SERVICE.", "SERVICE"),
+    ];
+
+    for (policy, expected) in test_cases {
+        let result = compiler.compile(policy);
+        assert_eq!(result.verdict, CompilationStatus::Pass, "Failed for: {}", policy);
+        assert_eq!(result.icae_constraints[0].subject, expected, "Failed for: {}", policy);
+    }
+}
+
+#[test]
+fn test_cost_ceiling_extracted() {
+    let compiler = PolicyCompiler::new();
+
+    let test_cases = vec![
+        ("Cost must not exceed 1000 USD per month by SERVICE.", 1000.0),
+        ("Cost must not exceed 1,500 EUR per month by SERVICE.", 1500.0),
+        ("Usage must not exceed 1k requests per hour by SERVICE.", 1000.0),
+        ("Usage quota must be capped at 250k tokens per day by SERVICE.", 250_000.0),
+    ];
+
+    for (policy, expected) in test_cases {
+        let result = compiler.compile(policy);
+        assert_eq!(result.verdict, CompilationStatus::Pass, "Failed for: {}", policy);
+        assert_eq!(result.icae_constraints[0].ceiling, Some(expected), "Failed for: {}", policy);
+    }
+}
+
+#[test]
+fn test_cost_without_limit_has_no_ceiling() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Token usage of 5000 tokens must be tracked by SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.icae_constraints[0].ceiling, None);
+}
+
+#[test]
+fn test_cost_exceed_without_number_fails() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Cost of logging must never exceed the USD budget by SERVICE.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::MissingCeiling { clause_index, span, .. } => {
+            assert_eq!(*clause_index, 0);
+            assert_eq!(span.text(policy), Some("exceed"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_cost_budget_periods() {
+    let compiler = PolicyCompiler::new();
+
+    let test_cases = vec![
+        ("Cost must not exceed 5 USD per request by SERVICE.", BudgetPeriod::PerRequest),
+        ("Usage must not exceed 1k requests per hour by SERVICE.", BudgetPeriod::Hourly),
+        ("Storage spend must not exceed 200 EUR per day by SERVICE.", BudgetPeriod::Daily),
+        ("Storage spend must not exceed 900 EUR weekly by SERVICE.", BudgetPeriod::Weekly),
+        ("Cost of logging cannot exceed 1000 USD per month by SERVICE.", BudgetPeriod::Monthly),
+        ("Storage spend must not exceed 3000 GBP per quarter by SERVICE.", BudgetPeriod::Quarterly),
+        ("Storage spend must not exceed 12k USD annually by SERVICE.", BudgetPeriod::Yearly),
+        ("Storage spend must not exceed 50k USD over the project lifetime by SERVICE.", BudgetPeriod::Lifetime),
+        ("Usage must not exceed 2M tokens/month by SERVICE.", BudgetPeriod::Monthly),
+    ];
+
+    for (policy, expected) in test_cases {
+        let result = compiler.compile(policy);
+        assert_eq!(result.verdict, CompilationStatus::Pass, "Failed for: {}", policy);
+        assert_eq!(result.icae_constraints[0].period, Some(expected), "Failed for: {}", policy);
+    }
+}
+
+#[test]
+fn test_cost_ceiling_without_period_fails() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of logging must not exceed 1000 USD by SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::AmbiguousBudgetPeriod { clause_index: 0, .. }
+    ));
+}
+
+#[test]
+fn test_cost_ceiling_with_conflicting_periods_fails() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Cost of logging must not exceed 1000 USD per day per month by SERVICE.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    let span = result.errors[0].span().expect("period error carries a span");
+    assert_eq!(span.text(policy), Some("month"));
+}
+
+#[test]
+fn test_cost_without_ceiling_needs_no_period() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost must be tracked in USD by SYSTEM.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.icae_constraints[0].period, None);
+}
+
 #[test]
 fn test_cost_with_tokens_unit() {
     let compiler = PolicyCompiler::new();
@@ -251,54 +648,1039 @@ fn test_missing_principal_fails() {
 }
 
 // =============================================================================
-// Cost Validation Tests
+// DIO Predicate Tests
 // =============================================================================
 
 #[test]
-fn test_cost_mention_without_explicit_unit_fails() {
+fn test_invariant_keeps_clause_and_requires_occurrence() {
     let compiler = PolicyCompiler::new();
-    let policy = "All actions must be logged by SYSTEM. Cost of logging cannot exceed $1000 per month by SERVICE.";
-    let result = compiler.compile(policy);
-    
-    assert_eq!(result.verdict, CompilationStatus::Fail);
-    assert!(matches!(
-        &result.errors[0],
-        CompilationError::MissingMeasurementUnit { clause_index: 1, .. }
-    ));
+    let result = compiler.compile("All actions must be logged by SYSTEM.");
+
+    let invariant = &result.dio_invariants[0];
+    assert_eq!(invariant.clause_text, "All actions must be logged by SYSTEM");
+    assert_eq!(invariant.predicate, InvariantPredicate::Count {
+        pattern: EventPattern { action: "log".to_string(), resource: Some("actions".to_string()) },
+        min: Some(1),
+        max: None,
+    });
+    assert!(invariant.predicate.evaluate(&[ExecutionEvent::new("log", "actions")]));
+    assert!(!invariant.predicate.evaluate(&[ExecutionEvent::new("export", "actions")]));
 }
 
 #[test]
-fn test_cost_mention_with_explicit_unit_passes() {
+fn test_invariant_required_fields_and_ordering() {
     let compiler = PolicyCompiler::new();
-    let policy = "All actions must be logged by SYSTEM. Cost of logging cannot exceed 1000 USD per month by SERVICE.";
-    let result = compiler.compile(policy);
-    
-    assert_eq!(result.verdict, CompilationStatus::Pass);
-    assert_eq!(result.icae_constraints.len(), 1);
+    let result = compiler.compile("SYSTEM must log access including actor id, timestamp before export.");
+
+    let predicate = &result.dio_invariants[0].predicate;
+    assert_eq!(predicate.to_string(), "count(log:access) >= 1 && log:access has [actor_id, timestamp] && log:access before export");
+
+    let logged = ExecutionEvent::new("log", "access").with_field("actor_id", "42").with_field("timestamp", "1700000000");
+    assert!(predicate.evaluate(&[logged.clone(), ExecutionEvent::new("export", "reports")]));
+    assert!(!predicate.evaluate(&[ExecutionEvent::new("export", "reports"), logged.clone()]));
+    assert!(!predicate.evaluate(&[ExecutionEvent::new("log", "access").with_field("timestamp", "1")]));
 }
 
-// =============================================================================
-// Traceability Tests
-// =============================================================================
+#[test]
+fn test_invariant_counts_and_prohibitions() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SERVICE must audit exports at least 3 times. Deny USER access to payroll.");
+
+    let audits = &result.dio_invariants[0].predicate;
+    let trace = vec![ExecutionEvent::new("audit", "exports"); 3];
+    assert!(audits.evaluate(&trace));
+    assert!(!audits.evaluate(&trace[..2]));
+
+    let denied = &result.dio_invariants[1].predicate;
+    assert!(denied.evaluate(&[]));
+    assert!(!denied.evaluate(&[ExecutionEvent::new("access", "payroll").with_principal("USER")]));
+}
 
 #[test]
-fn test_clause_exact_traceability() {
+fn test_ordered_clause_compiles_into_steps() {
     let compiler = PolicyCompiler::new();
-    let policy = "All actions must be logged by SYSTEM. No unauthorized access allowed by USER.";
-    let result = compiler.compile(policy);
+    let result = compiler.compile("SYSTEM must encrypt backups, then SYSTEM must upload backups and then must verify checksums.");
 
-    assert_eq!(result.verdict, CompilationStatus::Pass);
-    assert_eq!(result.traceability_map.len(), 2);
-    
-    // Verify clause IDs
-    assert_eq!(result.traceability_map[0].clause_id, "clause_0");
-    assert_eq!(result.traceability_map[1].clause_id, "clause_1");
-    
-    // Verify clause indices
-    assert_eq!(result.traceability_map[0].clause_index, 0);
-    assert_eq!(result.traceability_map[1].clause_index, 1);
-    
-    // Verify exact artifact mapping - no cross-contamination
+    let invariant = &result.dio_invariants[0];
+    let steps: Vec<(&str, &str, String)> = invariant.steps.iter()
+        .map(|step| (step.id.as_str(), step.text.as_str(), step.pattern.to_string()))
+        .collect();
+    assert_eq!(steps, vec![
+        ("dio_0_step_0", "SYSTEM must encrypt backups", "encrypt:backups".to_string()),
+        ("dio_0_step_1", "SYSTEM must upload backups", "upload:backups".to_string()),
+        ("dio_0_step_2", "must verify checksums", "verify:checksums".to_string()),
+    ]);
+    assert_eq!(invariant.precedence, vec![
+        PrecedenceEdge { before: "dio_0_step_0".to_string(), after: "dio_0_step_1".to_string() },
+        PrecedenceEdge { before: "dio_0_step_1".to_string(), after: "dio_0_step_2".to_string() },
+    ]);
+
+    let encrypt = ExecutionEvent::new("encrypt", "backups");
+    let upload = ExecutionEvent::new("upload", "backups");
+    let verify = ExecutionEvent::new("verify", "checksums");
+    assert!(invariant.predicate.evaluate(&[encrypt.clone(), upload.clone(), verify.clone()]));
+    assert!(!invariant.predicate.evaluate(&[upload.clone(), encrypt.clone(), verify.clone()]));
+    assert!(!invariant.predicate.evaluate(&[encrypt, upload]));
+}
+
+#[test]
+fn test_after_reverses_step_order() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must log access after login. Log actions then audit them by SYSTEM.");
+
+    let after = &result.dio_invariants[0];
+    assert_eq!(after.precedence, vec![PrecedenceEdge { before: "dio_0_step_1".to_string(), after: "dio_0_step_0".to_string() }]);
+    // Only the main step must occur; "after login" orders it
+    assert_eq!(after.predicate.to_string(), "count(log:access) >= 1 && login before log:access");
+
+    // "them" refers to the previous step's resource
+    let audited = &result.dio_invariants[1];
+    assert_eq!(audited.steps[1].pattern.to_string(), "audit:actions");
+}
+
+#[test]
+fn test_single_step_clause_has_no_steps() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must log access after 30 days.");
+
+    assert!(result.dio_invariants[0].steps.is_empty());
+    assert!(result.dio_invariants[0].precedence.is_empty());
+}
+
+// =============================================================================
+// Guard Tests
+// =============================================================================
+
+#[test]
+fn test_leading_condition_guards_every_artifact() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("If an incident is declared, SYSTEM must log access.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let guard = Guard::Condition { name: "incident_declared".to_string() };
+    assert_eq!(result.dio_invariants[0].guard, Some(guard.clone()));
+    assert_eq!(result.dio_invariants[0].clause_text, "If an incident is declared, SYSTEM must log access");
+    assert_eq!(result.zt_authority_graph[0].guard, Some(guard));
+    assert_eq!(result.zt_authority_graph[0].scope.to_string(), "log:access");
+}
+
+#[test]
+fn test_leading_condition_without_comma() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("When an incident is declared SYSTEM must log access.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let guard = Guard::Condition { name: "incident_declared".to_string() };
+    assert_eq!(result.dio_invariants[0].guard, Some(guard.clone()));
+    assert_eq!(result.zt_authority_graph[0].guard, Some(guard));
+    assert_eq!(result.zt_authority_graph[0].scope.to_string(), "log:access");
+
+    let result = compiler.compile("If the request is external USER must not read payroll.");
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let authority = &result.zt_authority_graph[0];
+    assert_eq!(authority.guard, Some(Guard::Condition { name: "request_external".to_string() }));
+    assert_eq!(authority.scope.to_string(), "read:payroll");
+    assert_eq!(authority.polarity, Polarity::Prohibition);
+}
+
+#[test]
+fn test_unterminated_leading_condition_is_rejected() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("When an incident is declared access must be logged by SYSTEM.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(result.errors[0], CompilationError::UnterminatedCondition { clause_index: 0, .. }));
+}
+
+#[test]
+fn test_exception_guards_and_conditional_conjunctions() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Deny USER access to payroll. \
+                  Allow USER access to payroll except when an audit is running or the payroll is locked.";
+    let result = compiler.compile(policy);
+
+    // Differently guarded clauses do not conflict, and "or" inside a condition is not a second action
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph[0].guard, None);
+    let guard = result.zt_authority_graph[1].guard.as_ref().unwrap();
+    assert_eq!(guard.to_string(), "!(audit_running || payroll_locked)");
+}
+
+#[test]
+fn test_evaluator_applies_guarded_artifacts_under_conditions() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of inference cannot exceed 100 USD per day by SERVICE when surge pricing is active.");
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.icae_constraints[0].subject, "inference");
+    let event = PolicyEvent::Cost(CostEvent {
+        subject: "inference".to_string(),
+        amount: 150.0,
+        unit: MeasurementUnit::Usd,
+        timestamp: None,
+    });
+    let cost_violations = |mut evaluator: Evaluator<'_>| {
+        evaluator.observe(&event).iter().filter(|v| v.kind == ArtifactKind::Icae).count()
+    };
+
+    assert_eq!(cost_violations(Evaluator::new(&result)), 0);
+    assert_eq!(cost_violations(Evaluator::new(&result).with_conditions(["surge_pricing_active"])), 1);
+}
+
+// =============================================================================
+// Runtime Evaluation Tests
+// =============================================================================
+
+fn authorization(principal: &str, action: &str, resource: &str) -> PolicyEvent {
+    PolicyEvent::Authorization(AuthorizationEvent {
+        principal: principal.to_string(),
+        action: action.to_string(),
+        resource: resource.to_string(),
+    })
+}
+
+#[test]
+fn test_artifacts_carry_failure_signals() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must request access then SYSTEM must approve. Cost of logging cannot exceed 1000 USD per month by SERVICE.");
+
+    assert_eq!(result.zt_authority_graph[0].failure_signal, "VIOLATION_ZT_0_0");
+    assert_eq!(result.zt_authority_graph[2].failure_signal, "VIOLATION_ZT_1");
+    assert_eq!(result.icae_constraints[0].failure_signal, "VIOLATION_ICAE_1");
+}
+
+#[test]
+fn test_evaluator_checks_authorizations() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must read customer records. Deny SERVICE access to payroll. SYSTEM must allow access to payroll.");
+    let mut evaluator = Evaluator::new(&result);
+
+    assert!(evaluator.observe(&authorization("USER", "read", "customer_records")).is_empty());
+    assert!(evaluator.observe(&authorization("system", "access", "payroll")).is_empty());
+    assert!(evaluator.observe(&authorization("USER", "export", "reports")).is_empty());
+
+    let denied = evaluator.observe(&authorization("SERVICE", "access", "payroll"));
+    assert_eq!(denied.len(), 1);
+    assert_eq!(denied[0].kind, ArtifactKind::Zt);
+    assert_eq!(denied[0].artifact_id, "zt_auth_1");
+    assert_eq!(denied[0].failure_signal, "VIOLATION_ZT_1");
+    assert_eq!(denied[0].event_index, Some(3));
+
+    let unnamed = evaluator.observe(&authorization("SERVICE", "read", "customer_records"));
+    assert_eq!(unnamed[0].clause_id, "clause_0");
+}
+
+#[test]
+fn test_evaluator_stops_authorizing_revoked_authority() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must read reports. Revoke USER access after 30 days.");
+    let mut evaluator = Evaluator::new(&result);
+
+    assert!(evaluator.observe(&authorization("USER", "read", "reports")).is_empty());
+    evaluator.revoke(RevocationTrigger::After { duration: TimeSpan { amount: 2, unit: TimeUnit::Weeks } });
+    assert!(evaluator.observe(&authorization("USER", "read", "reports")).is_empty());
+
+    evaluator.revoke(RevocationTrigger::After { duration: TimeSpan { amount: 1, unit: TimeUnit::Months } });
+    let revoked = evaluator.observe(&authorization("USER", "read", "reports"));
+    assert_eq!(revoked.len(), 1);
+    assert_eq!(revoked[0].artifact_id, "zt_auth_0");
+    assert_eq!(revoked[0].reason, "USER's authority for read:reports was revoked");
+}
+
+#[test]
+fn test_evaluator_checks_invariants_over_trace() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("All actions must be logged by SYSTEM. Deny USER access to payroll.");
+    let events = vec![
+        PolicyEvent::Execution(ExecutionEvent::new("access", "payroll")),
+        PolicyEvent::Execution(ExecutionEvent::new("access", "payroll")),
+    ];
+
+    let violations = Evaluator::evaluate(&result, &events);
+    let reported: Vec<(&str, Option<usize>)> = violations.iter()
+        .map(|v| (v.failure_signal.as_str(), v.event_index))
+        .collect();
+    assert_eq!(reported, vec![("VIOLATION_DIO_1", Some(0)), ("VIOLATION_DIO_0", None)]);
+    assert_eq!(violations[1].clause_id, "clause_0");
+}
+
+#[test]
+fn test_evaluator_accumulates_costs() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Cost of logging cannot exceed 1000 USD per month by SERVICE. \
+                  Cost of inference cannot exceed 2 USD per request by SERVICE.";
+    let result = compiler.compile(policy);
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let cost = |subject: &str, amount: f64| PolicyEvent::Cost(CostEvent {
+        subject: subject.to_string(),
+        amount,
+        unit: MeasurementUnit::Usd,
+        timestamp: None,
+    });
+    let mut evaluator = Evaluator::new(&result);
+
+    assert!(evaluator.observe(&cost("logging", 600.0)).is_empty());
+    assert!(evaluator.observe(&cost("Logging", 300.0)).is_empty());
+    let exceeded = evaluator.observe(&cost("logging", 200.0));
+    assert_eq!(exceeded[0].failure_signal, "VIOLATION_ICAE_0");
+    assert!(evaluator.observe(&cost("logging", 50.0)).is_empty());
+
+    assert!(evaluator.observe(&cost("inference", 1.5)).is_empty());
+    assert_eq!(evaluator.observe(&cost("inference", 2.5)).len(), 1);
+    assert_eq!(evaluator.observe(&cost("inference", 3.0)).len(), 1);
+}
+
+#[test]
+fn test_evaluator_resets_costs_per_window() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of logging cannot exceed 100 USD per day by SERVICE.");
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let cost = |amount: f64, timestamp: u64| PolicyEvent::Cost(CostEvent {
+        subject: "logging".to_string(),
+        amount,
+        unit: MeasurementUnit::Usd,
+        timestamp: Some(timestamp),
+    });
+
+    let mut evaluator = Evaluator::new(&result);
+    assert!(evaluator.observe(&cost(80.0, 3_600)).is_empty());
+    // The next day starts a new window
+    assert!(evaluator.observe(&cost(80.0, 90_000)).is_empty());
+    let exceeded = evaluator.observe(&cost(30.0, 93_600));
+    assert_eq!(exceeded.len(), 1);
+    assert_eq!(exceeded[0].kind, ArtifactKind::Icae);
+}
+
+// =============================================================================
+// Cost Ledger Tests
+// =============================================================================
+
+fn usage(subject: &str, amount: f64, timestamp: u64) -> UsageRecord {
+    UsageRecord { subject: subject.to_string(), amount, unit: MeasurementUnit::Usd, timestamp }
+}
+
+#[test]
+fn test_ledger_reports_approaching_then_exceeded() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of inference cannot exceed 1000 USD per month by SERVICE.");
+    let mut ledger = CostLedger::new(&result.icae_constraints);
+    // 2024-05-10 and 2024-05-20
+    let (may_10, may_20) = (1_715_299_200, 1_716_163_200);
+
+    assert!(ledger.record(&usage("inference", 700.0, may_10)).is_empty());
+    let approaching = ledger.record(&usage("inference", 150.0, may_10));
+    assert_eq!(approaching.len(), 1);
+    assert_eq!(approaching[0].level, AlertLevel::Approaching);
+    assert_eq!(approaching[0].window, BudgetWindow { start: 1_714_521_600, end: 1_717_200_000 });
+    assert!(ledger.record(&usage("inference", 50.0, may_20)).is_empty());
+
+    let exceeded = ledger.record(&usage("Inference", 200.0, may_20));
+    assert_eq!(exceeded[0].level, AlertLevel::Exceeded);
+    assert_eq!(exceeded[0].failure_signal, "VIOLATION_ICAE_0");
+    assert_eq!(exceeded[0].total, 1100.0);
+    assert_eq!(ledger.remaining("icae_0", may_20), Some(-100.0));
+    assert!(ledger.record(&usage("inference", 10.0, may_20)).is_empty());
+}
+
+#[test]
+fn test_ledger_windows_follow_calendar() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of inference cannot exceed 1000 USD per month by SERVICE.");
+    let mut ledger = CostLedger::new(&result.icae_constraints).with_warning_ratio(1.0);
+    // 2024-01-31T23:00:00Z and 2024-02-01T01:00:00Z
+    let (january, february) = (1_706_742_000, 1_706_749_200);
+
+    assert!(ledger.record(&usage("inference", 900.0, january)).is_empty());
+    assert!(ledger.record(&usage("inference", 900.0, february)).is_empty());
+    assert_eq!(ledger.total("icae_0", january), 900.0);
+    assert_eq!(ledger.total("icae_0", february), 900.0);
+    // Usage in other units or for other subjects does not count
+    assert!(ledger.record(&UsageRecord { unit: MeasurementUnit::Eur, ..usage("inference", 500.0, february) }).is_empty());
+    assert!(ledger.record(&usage("storage", 500.0, february)).is_empty());
+    assert_eq!(ledger.total("icae_0", february), 900.0);
+}
+
+#[test]
+fn test_ledger_charges_principal_budget() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost must not exceed 1000 USD per month by SERVICE.");
+    let mut ledger = CostLedger::new(&result.icae_constraints);
+    // 2024-05-10
+    let may_10 = 1_715_299_200;
+
+    assert!(ledger.record(&usage("month", 5000.0, may_10)).is_empty());
+    let exceeded = ledger.record(&usage("SERVICE", 1200.0, may_10));
+    assert_eq!(exceeded.len(), 1);
+    assert_eq!(exceeded[0].level, AlertLevel::Exceeded);
+    assert_eq!(ledger.total("icae_0", may_10), 1200.0);
+}
+
+#[test]
+fn test_ledger_accepts_timestamps_near_the_end_of_time() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of inference cannot exceed 10 USD per request by SERVICE. \
+                                   Cost of storage cannot exceed 10 USD per hour by SERVICE.");
+    let mut ledger = CostLedger::new(&result.icae_constraints);
+
+    assert_eq!(ledger.record(&usage("inference", 20.0, u64::MAX)).len(), 1);
+    assert_eq!(ledger.record(&usage("storage", 20.0, u64::MAX)).len(), 1);
+    assert_eq!(ledger.total("icae_1", u64::MAX), 20.0);
+}
+
+// =============================================================================
+// Authority Scope Tests
+// =============================================================================
+
+#[test]
+fn test_scope_from_active_and_passive_clauses() {
+    let compiler = PolicyCompiler::new();
+    let policy = "USER must read customer records. All reports must be logged by SYSTEM.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let scopes: Vec<String> = result.zt_authority_graph.iter().map(|a| a.scope.to_string()).collect();
+    assert_eq!(scopes, vec!["read:customer_records", "log:reports"]);
+}
+
+#[test]
+fn test_scope_of_access_verbs() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SERVICE must allow export of reports. Deny USER access to payroll.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let scopes: Vec<&AuthorityScope> = result.zt_authority_graph.iter().map(|a| &a.scope).collect();
+    assert_eq!(scopes[0], &AuthorityScope { action: "export".to_string(), resource: "reports".to_string() });
+    assert_eq!(scopes[1].to_string(), "access:payroll");
+}
+
+#[test]
+fn test_scope_is_shared_by_clause_edges() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must request access then SYSTEM must approve.");
+
+    assert_eq!(result.zt_authority_graph[0].scope, result.zt_authority_graph[1].scope);
+    assert_eq!(result.zt_authority_graph[1].scope.to_string(), "request:access");
+}
+
+#[test]
+fn test_authority_without_resource_is_rejected() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must approve.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::MissingResource { clause_index: 0, .. }
+    ));
+}
+
+#[test]
+fn test_trigger_phrases_are_not_resources() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SERVICE must allow access on role change. \
+                  USER must allow access to payroll until revoked after 30 days.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let scopes: Vec<String> = result.zt_authority_graph.iter().map(|a| a.scope.to_string()).collect();
+    assert_eq!(scopes, vec!["allow:access", "access:payroll"]);
+}
+
+#[test]
+fn test_markers_and_abbreviations_are_not_resources() {
+    let compiler = PolicyCompiler::new();
+    let policy = "Assumes network must be secure by SYSTEM. \
+                  USER must read files, e.g. audit records. \
+                  SERVICE must read reports, excluding drafts.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let scopes: Vec<String> = result.zt_authority_graph.iter().map(|a| a.scope.to_string()).collect();
+    assert_eq!(scopes, vec!["secure:network", "read:files_audit_records", "read:reports"]);
+}
+
+#[test]
+fn test_cost_clause_governs_its_subject() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Cost of inference must not exceed 100 USD per month by SERVICE.");
+
+    // The cost constraint is the clause's only invariant
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert!(result.dio_invariants.is_empty());
+    assert_eq!(result.icae_constraints.len(), 1);
+    assert_eq!(result.zt_authority_graph[0].scope.to_string(), "exceed:inference");
+}
+
+#[test]
+fn test_clause_without_action_is_not_missing_a_resource() {
+    let compiler = PolicyCompiler::with_diagnostic_mode(DiagnosticMode::ReportAll);
+    let result = compiler.compile("The system is secure by SYSTEM.");
+
+    assert_eq!(result.errors.len(), 1);
+    assert!(matches!(result.errors[0], CompilationError::MissingActionVerb { clause_index: 0, .. }));
+}
+
+// =============================================================================
+// Polarity Tests
+// =============================================================================
+
+#[test]
+fn test_polarity_detected_and_carried_on_artifacts() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SYSTEM must log access. SYSTEM must never audit reports. USER can record reports. Deny USER access to payroll.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let expected = vec![Polarity::Obligation, Polarity::Prohibition, Polarity::Permission, Polarity::Prohibition];
+    assert_eq!(result.intent_normalization.polarities, expected);
+    let authorities: Vec<Polarity> = result.zt_authority_graph.iter().map(|a| a.polarity).collect();
+    assert_eq!(authorities, expected);
+
+    let never = &result.dio_invariants[1];
+    assert_eq!(never.polarity, Polarity::Prohibition);
+    assert_eq!(never.predicate.to_string(), "count(audit:reports) <= 0");
+    assert!(result.dio_invariants[2].predicate.evaluate(&[]));
+}
+
+#[test]
+fn test_no_before_allowing_verb_is_prohibition() {
+    assert_eq!(Polarity::from_clause("No unauthorized access allowed by USER"), Polarity::Prohibition);
+    assert_eq!(Polarity::from_clause("Allow USER access with no approval"), Polarity::Permission);
+
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("No unauthorized access allowed by USER.");
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph[0].polarity, Polarity::Prohibition);
+}
+
+#[test]
+fn test_permission_modal_is_an_action() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER can read reports. SERVICE cannot export payroll. Reports can be audited by SYSTEM.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.intent_normalization.polarities, vec![Polarity::Permission, Polarity::Prohibition, Polarity::Permission]);
+    let scopes: Vec<String> = result.zt_authority_graph.iter().map(|a| a.scope.to_string()).collect();
+    assert_eq!(scopes, vec!["read:reports", "export:payroll", "audit:reports"]);
+
+    let result = compiler.compile("USER can.");
+    assert!(matches!(result.errors[0], CompilationError::MissingActionVerb { clause_index: 0, .. }));
+}
+
+#[test]
+fn test_obligation_and_prohibition_conflict() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must log access. SYSTEM must not log access.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(result.errors[0], CompilationError::ConflictingClauses { a: 0, b: 1, .. }));
+}
+
+#[test]
+fn test_permission_and_obligation_do_not_conflict() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER can log access. USER must log access. SYSTEM must log access unless the user is not an admin.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    // Negation inside a condition does not negate the clause
+    assert_eq!(result.intent_normalization.polarities[2], Polarity::Obligation);
+}
+
+// =============================================================================
+// Conflict Detection Tests
+// =============================================================================
+
+#[test]
+fn test_allow_and_deny_same_subject_conflict() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SERVICE must allow export of data. All actions must be logged by SYSTEM. SERVICE must deny export of data.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::ConflictingClauses { a, b, span } => {
+            assert_eq!((*a, *b), (0, 2));
+            assert_eq!(span.text(policy), Some("SERVICE must deny export of data"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_allow_and_deny_for_different_principals_compiles() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SERVICE must allow export of data. USER must deny export of data.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph[0].effect, Some(AccessEffect::Allow));
+    assert_eq!(result.zt_authority_graph[1].effect, Some(AccessEffect::Deny));
+}
+
+#[test]
+fn test_allow_and_deny_for_different_subjects_compiles() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SERVICE must allow export of reports. SERVICE must deny export of data.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+}
+
+#[test]
+fn test_repeated_allow_is_not_a_conflict() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SERVICE must allow export of data. SERVICE must allow export of data.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+}
+
+// =============================================================================
+// Principal Registry Tests
+// =============================================================================
+
+#[test]
+fn test_builtin_principal_identity() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("All actions must be logged by SYSTEM.");
+
+    assert_eq!(result.zt_authority_graph[0].identity, "SYSTEM");
+}
+
+#[test]
+fn test_registered_principals_resolve_to_identity() {
+    let compiler = PolicyCompiler::builder()
+        .principal("billing-service", Principal::Service)
+        .principal("ROLE:auditor", Principal::User)
+        .principal("svc:ingest", Principal::Service)
+        .build();
+    let policy = "Invoices must be logged by billing-service. \
+                  Exports must be audited by ROLE:auditor. \
+                  Uploads must be recorded by svc:ingest.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let identities: Vec<(&str, Principal)> = result.zt_authority_graph.iter()
+        .map(|a| (a.identity.as_str(), a.principal))
+        .collect();
+    assert_eq!(identities, vec![
+        ("billing-service", Principal::Service),
+        ("ROLE:auditor", Principal::User),
+        ("svc:ingest", Principal::Service),
+    ]);
+}
+
+#[test]
+fn test_unregistered_named_principal_fails() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Invoices must be logged by billing-service.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::MissingPrincipal { .. }
+    ));
+}
+
+#[test]
+fn test_registry_replaced_through_builder() {
+    let registry = PrincipalRegistry::new().with("svc:ingest", Principal::Service);
+    let compiler = PolicyCompiler::builder().principal_registry(registry.clone()).build();
+
+    assert_eq!(compiler.principal_registry(), &registry);
+    assert!(compiler.compile("Uploads must be recorded by svc:ingest.").is_success());
+}
+
+#[test]
+fn test_conflicts_compare_specific_identities() {
+    let compiler = PolicyCompiler::builder()
+        .principal("billing-service", Principal::Service)
+        .principal("svc:ingest", Principal::Service)
+        .build();
+
+    let distinct = compiler.compile("billing-service must allow export of data. svc:ingest must deny export of data.");
+    assert_eq!(distinct.verdict, CompilationStatus::Pass);
+
+    let same = compiler.compile("billing-service must allow export of data. billing-service must deny export of data.");
+    assert!(matches!(
+        &same.errors[0],
+        CompilationError::ConflictingClauses { a: 0, b: 1, .. }
+    ));
+}
+
+// =============================================================================
+// Principal Role Tests
+// =============================================================================
+
+#[test]
+fn test_single_principal_is_actor() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("All actions must be logged by SYSTEM.");
+
+    assert_eq!(result.zt_authority_graph.len(), 1);
+    assert_eq!(result.zt_authority_graph[0].id, "zt_auth_0");
+    assert_eq!(result.zt_authority_graph[0].role, AuthorityRole::Actor);
+}
+
+#[test]
+fn test_every_principal_in_clause_gets_an_edge() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must request access then SYSTEM must approve.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let edges: Vec<(&str, &str, AuthorityRole)> = result.zt_authority_graph.iter()
+        .map(|a| (a.id.as_str(), a.identity.as_str(), a.role))
+        .collect();
+    assert_eq!(edges, vec![
+        ("zt_auth_0_0", "USER", AuthorityRole::Actor),
+        ("zt_auth_0_1", "SYSTEM", AuthorityRole::Approver),
+    ]);
+    assert_eq!(result.traceability_map[0].authority_ids, vec!["zt_auth_0_0", "zt_auth_0_1"]);
+}
+
+#[test]
+fn test_passive_agent_and_delegate_roles() {
+    let compiler = PolicyCompiler::builder()
+        .principal("ROLE:auditor", Principal::User)
+        .build();
+    let policy = "SERVICE must log exports then be audited by ROLE:auditor. \
+                  USER must delegate access to SERVICE.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let roles: Vec<(&str, AuthorityRole)> = result.zt_authority_graph.iter()
+        .map(|a| (a.identity.as_str(), a.role))
+        .collect();
+    assert_eq!(roles, vec![
+        ("SERVICE", AuthorityRole::Actor),
+        ("ROLE:auditor", AuthorityRole::Auditor),
+        ("USER", AuthorityRole::Actor),
+        ("SERVICE", AuthorityRole::Delegate),
+    ]);
+}
+
+#[test]
+fn test_undeterminable_role_is_rejected() {
+    let compiler = PolicyCompiler::new();
+    let policy = "USER must review SERVICE access.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::AmbiguousPrincipalRole { clause_index, principal, span, .. } => {
+            assert_eq!(*clause_index, 0);
+            assert_eq!(principal, "SERVICE");
+            assert_eq!(span.text(policy), Some("SERVICE"));
+        }
+        other => panic!("expected AmbiguousPrincipalRole, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_roles_bound_per_step_of_ordered_clause() {
+    let compiler = PolicyCompiler::new();
+    for policy in [
+        "SYSTEM must log access before USER exports reports.",
+        "SYSTEM must log access after USER exports reports.",
+    ] {
+        let result = compiler.compile(policy);
+
+        assert_eq!(result.verdict, CompilationStatus::Pass, "{}", policy);
+        let edges: Vec<(&str, AuthorityRole)> = result.zt_authority_graph.iter()
+            .map(|a| (a.identity.as_str(), a.role))
+            .collect();
+        assert_eq!(edges, vec![("SYSTEM", AuthorityRole::Actor), ("USER", AuthorityRole::Actor)]);
+        assert_eq!(result.dio_invariants[0].steps.len(), 2);
+    }
+}
+
+#[test]
+fn test_step_with_two_principals_still_needs_roles() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must log access before USER exports SERVICE reports.");
+
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::AmbiguousPrincipalRole { principal, .. } if principal == "USER"
+    ));
+}
+
+#[test]
+fn test_repeated_principal_role_is_one_edge() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must log requests then SYSTEM must log responses.");
+
+    assert_eq!(result.zt_authority_graph.len(), 1);
+    assert_eq!(result.zt_authority_graph[0].id, "zt_auth_0");
+}
+
+// =============================================================================
+// Delegation Graph Tests
+// =============================================================================
+
+#[test]
+fn test_delegation_compiles_typed_edge() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM delegates audit authority to SERVICE with max depth 1 for 30 days.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let edge = &result.delegation_graph.edges[0];
+    assert_eq!(edge.id, "zt_deleg_0");
+    assert_eq!((edge.from.as_str(), edge.to.as_str()), ("SYSTEM", "SERVICE"));
+    assert_eq!(edge.scope, "audit authority");
+    assert_eq!(edge.max_depth, Some(1));
+    assert_eq!(edge.expiry, Some(TimeSpan { amount: 30, unit: TimeUnit::Days }));
+
+    let delegator = &result.zt_authority_graph[0];
+    assert_eq!(delegator.identity, "SYSTEM");
+    assert_eq!(delegator.delegation_rules, vec![edge.clone()]);
+    assert!(result.zt_authority_graph[1].delegation_rules.is_empty());
+}
+
+#[test]
+fn test_delegation_without_limits() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must delegate access to SERVICE.");
+
+    let edge = &result.delegation_graph.edges[0];
+    assert_eq!(edge.scope, "access");
+    assert_eq!(edge.max_depth, None);
+    assert_eq!(edge.expiry, None);
+}
+
+#[test]
+fn test_delegation_graph_adjacency_and_paths() {
+    let compiler = PolicyCompiler::builder()
+        .principal("svc:ingest", Principal::Service)
+        .build();
+    let policy = "SYSTEM delegates audit authority to SERVICE. \
+                  SERVICE delegates audit authority to svc:ingest. \
+                  SYSTEM delegates export authority to USER.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let graph = &result.delegation_graph;
+    assert_eq!(graph.delegates_of("SYSTEM"), ["SERVICE", "USER"]);
+    assert_eq!(graph.delegates_of("svc:ingest"), [] as [String; 0]);
+    assert_eq!(graph.path("SYSTEM", "svc:ingest"), Some(vec![
+        "SYSTEM".to_string(), "SERVICE".to_string(), "svc:ingest".to_string(),
+    ]));
+    assert_eq!(graph.path("USER", "SYSTEM"), None);
+}
+
+#[test]
+fn test_delegation_cycle_is_rejected() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SYSTEM delegates audit authority to SERVICE. \
+                  SERVICE delegates audit authority to USER. \
+                  USER delegates audit authority to SYSTEM.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::DelegationCycle { clause_index, cycle, .. } => {
+            assert_eq!(*clause_index, 2);
+            assert_eq!(cycle, &["USER", "SYSTEM", "SERVICE", "USER"]);
+        }
+        other => panic!("expected DelegationCycle, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_delegation_without_delegator_is_incomplete() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Audit authority must be delegated to SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::IncompleteDelegation { clause_index: 0, .. }
+    ));
+}
+
+// =============================================================================
+// Revocation Trigger Tests
+// =============================================================================
+
+#[test]
+fn test_policy_change_is_always_a_trigger() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("All actions must be logged by SYSTEM.");
+
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers, vec![RevocationTrigger::PolicyChange]);
+}
+
+#[test]
+fn test_revocation_after_duration() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must read reports. Revoke USER access after 30 days.");
+
+    // The revocation bounds USER's existing authority instead of compiling its own
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph.len(), 1);
+    assert_eq!(result.zt_authority_graph[0].scope.to_string(), "read:reports");
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers, vec![
+        RevocationTrigger::PolicyChange,
+        RevocationTrigger::After { duration: TimeSpan { amount: 30, unit: TimeUnit::Days } },
+    ]);
+    assert_eq!(result.dio_invariants.len(), 1);
+    assert!(result.traceability_map[1].authority_ids.is_empty());
+}
+
+#[test]
+fn test_revocation_on_role_change_and_budget() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SERVICE must export reports. \
+                  SERVICE access must be revoked on role change. \
+                  Revoke SERVICE access when budget exceeded.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph.len(), 1);
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers, vec![
+        RevocationTrigger::PolicyChange,
+        RevocationTrigger::RoleChange,
+        RevocationTrigger::BudgetExceeded,
+    ]);
+    assert!(result.icae_constraints.is_empty());
+}
+
+#[test]
+fn test_revocation_on_role_change_bounds_existing_access() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must read reports. Revoke USER access on role change.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph.len(), 1);
+    assert_eq!(result.zt_authority_graph[0].scope.to_string(), "read:reports");
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers[1], RevocationTrigger::RoleChange);
+}
+
+#[test]
+fn test_revocation_of_named_resource() {
+    let compiler = PolicyCompiler::new();
+    let policy = "USER must read reports. USER must read payroll. Deny SERVICE access to payroll. \
+                  Revoke USER access to payroll on role change.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let triggers: Vec<&[RevocationTrigger]> = result.zt_authority_graph.iter()
+        .map(|a| a.revocation_triggers.as_slice())
+        .collect();
+    assert_eq!(triggers, vec![
+        &[RevocationTrigger::PolicyChange][..],
+        &[RevocationTrigger::PolicyChange, RevocationTrigger::RoleChange][..],
+        &[RevocationTrigger::PolicyChange][..],
+    ]);
+}
+
+#[test]
+fn test_revocation_without_authority_is_standalone() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Deny USER access to payroll. Revoke USER access after 30 days.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers, vec![RevocationTrigger::PolicyChange]);
+    assert_eq!(result.revocation_rules.len(), 1);
+    assert_eq!(result.revocation_rules[0].clause_index, 1);
+    assert_eq!(result.traceability_map[1].revocation_ids, vec!["revocation_1"]);
+}
+
+#[test]
+fn test_revocation_clause_compiles_on_its_own() {
+    let compiler = PolicyCompiler::new();
+
+    let result = compiler.compile("Revoke USER access after 30 days.");
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let rule = &result.revocation_rules[0];
+    assert_eq!(rule.identities, vec!["USER"]);
+    assert_eq!(rule.resource, None);
+    assert_eq!(rule.triggers, vec![RevocationTrigger::After { duration: TimeSpan { amount: 30, unit: TimeUnit::Days } }]);
+
+    // Naming no principal revokes every principal
+    let result = compiler.compile("Revoke on role change.");
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let rule = &result.revocation_rules[0];
+    assert!(rule.identities.is_empty());
+    assert_eq!(rule.resource, None);
+    assert_eq!(rule.triggers, vec![RevocationTrigger::RoleChange]);
+}
+
+#[test]
+fn test_revocation_without_principal_revokes_every_grant() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must read reports. SYSTEM must log access. Revoke on role change.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert!(result.revocation_rules.is_empty());
+    for authority in &result.zt_authority_graph {
+        assert_eq!(authority.revocation_triggers, vec![RevocationTrigger::PolicyChange, RevocationTrigger::RoleChange]);
+    }
+}
+
+#[test]
+fn test_grant_keeps_its_own_revocation_triggers() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must allow access to payroll until revoked on role change.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.zt_authority_graph[0].scope.to_string(), "access:payroll");
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers[1], RevocationTrigger::RoleChange);
+}
+
+#[test]
+fn test_conditions_without_revocation_are_ignored() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("SYSTEM must log access after 30 days.");
+
+    assert_eq!(result.zt_authority_graph[0].revocation_triggers, vec![RevocationTrigger::PolicyChange]);
+}
+
+// =============================================================================
+// Cost Validation Tests
+// =============================================================================
+
+#[test]
+fn test_cost_mention_without_explicit_unit_fails() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM. Cost of logging cannot exceed $1000 per month by SERVICE.";
+    let result = compiler.compile(policy);
+    
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::MissingMeasurementUnit { clause_index: 1, .. }
+    ));
+}
+
+#[test]
+fn test_cost_mention_with_explicit_unit_passes() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM. Cost of logging cannot exceed 1000 USD per month by SERVICE.";
+    let result = compiler.compile(policy);
+    
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.icae_constraints.len(), 1);
+}
+
+// =============================================================================
+// Traceability Tests
+// =============================================================================
+
+#[test]
+fn test_clause_exact_traceability() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM. No unauthorized access allowed by USER.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.traceability_map.len(), 2);
+    
+    // Verify clause IDs
+    assert_eq!(result.traceability_map[0].clause_id, "clause_0");
+    assert_eq!(result.traceability_map[1].clause_id, "clause_1");
+    
+    // Verify clause indices
+    assert_eq!(result.traceability_map[0].clause_index, 0);
+    assert_eq!(result.traceability_map[1].clause_index, 1);
+    
+    // Verify exact artifact mapping - no cross-contamination
     assert!(result.traceability_map[0].invariant_ids.contains(&"dio_0".to_string()));
     assert!(!result.traceability_map[0].invariant_ids.contains(&"dio_1".to_string()));
     assert!(result.traceability_map[1].invariant_ids.contains(&"dio_1".to_string()));
@@ -320,6 +1702,401 @@ fn test_traceability_includes_clause_text() {
     assert!(result.traceability_map[0].clause_text.contains("logged"));
 }
 
+#[test]
+fn test_outline_numbers_become_clause_ids() {
+    let compiler = PolicyCompiler::new();
+    let policy = "1. Access\n1.1 All actions must be logged by SYSTEM.\n1.2 Records must be audited by USER.\n2. Keys must be rotated by SERVICE. Backups must be encrypted by SYSTEM.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let ids: Vec<&str> = result.traceability_map.iter().map(|e| e.clause_id.as_str()).collect();
+    assert_eq!(ids, vec!["clause_1.1", "clause_1.2", "clause_2_0", "clause_2_1"]);
+    assert_eq!(result.traceability_map[0].clause_text, "All actions must be logged by SYSTEM");
+    assert_eq!(result.traceability_map[0].invariant_ids, vec!["dio_0".to_string()]);
+}
+
+#[test]
+fn test_outline_hierarchy_is_preserved() {
+    let compiler = PolicyCompiler::new();
+    let policy = "1. Access\n1.1 Logging\n1.1.1 All actions must be logged by SYSTEM.\n1.2 Records must be audited by USER.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let sections = &result.intent_normalization.sections;
+    let numbers: Vec<&str> = sections.iter().map(|s| s.number.as_str()).collect();
+    assert_eq!(numbers, vec!["1", "1.1", "1.1.1", "1.2"]);
+    let headings: Vec<Option<&str>> = sections.iter().map(|s| s.heading.as_deref()).collect();
+    assert_eq!(headings, vec![Some("Access"), Some("Logging"), None, None]);
+    let parents: Vec<Option<&str>> = sections.iter().map(|s| s.parent.as_deref()).collect();
+    assert_eq!(parents, vec![None, Some("1"), Some("1.1"), Some("1")]);
+    assert_eq!(sections[2].clause_indices, vec![0]);
+    assert_eq!(sections[3].clause_indices, vec![1]);
+    assert_eq!(sections[2].span.text(policy), Some("1.1.1"));
+    assert_eq!(result.intent_normalization.clauses.len(), 2);
+}
+
+#[test]
+fn test_repeated_section_number_is_rejected() {
+    let compiler = PolicyCompiler::new();
+    let policy = "1.1 All actions must be logged by SYSTEM.\n1.2 Records must be audited by USER.\n1.1 USER must read reports.";
+    let result = compiler.compile(policy);
+
+    // Both items would otherwise be traced as clause_1.1
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::DuplicateSection { number, span } => {
+            assert_eq!(number, "1.1");
+            assert_eq!(span.text(policy), Some("1.1"));
+            assert_eq!(span.line, 3);
+        }
+        other => panic!("expected DuplicateSection, got {:?}", other),
+    }
+}
+
+// =============================================================================
+// Markdown Input Tests
+// =============================================================================
+
+#[test]
+fn test_markdown_document_compiles() {
+    let compiler = PolicyCompiler::new();
+    let document = "\
+# Data Handling
+
+Policies below apply to production.
+
+## Logging
+
+- All actions must be logged by **SYSTEM**.
+- Records must be audited by USER.
+
+```text
+USER must delete all logs.
+```
+
+## Costs
+
+| Subject | Rule |
+|---------|------|
+| Logging | Cost of logging cannot exceed 1000 USD per month by SERVICE |
+";
+    let result = compiler.compile_markdown(document);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail, "prose without a principal is still compiled");
+
+    let document = document.replace("Policies below apply to production.", "<!-- Policies below apply to production. -->");
+    let result = compiler.compile_markdown(&document);
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.intent_normalization.clauses.len(), 3);
+    assert_eq!(result.icae_constraints[0].ceiling, Some(1000.0));
+
+    let ids: Vec<&str> = result.traceability_map.iter().map(|e| e.clause_id.as_str()).collect();
+    assert_eq!(ids, vec!["clause_1.1_0", "clause_1.1_1", "clause_1.2"]);
+    let lines: Vec<usize> = result.traceability_map.iter().map(|e| e.span.line).collect();
+    assert_eq!(lines, vec![7, 8, 18]);
+    assert_eq!(result.traceability_map[1].span.text(&document), Some("Records must be audited by USER"));
+}
+
+#[test]
+fn test_markdown_headings_are_sections() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile_markdown("# Access\n## Logging\nAll actions must be logged by SYSTEM.\n");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    let sections = &result.intent_normalization.sections;
+    assert_eq!(sections.len(), 2);
+    assert_eq!((sections[1].number.as_str(), sections[1].heading.as_deref()), ("1.1", Some("Logging")));
+    assert_eq!(sections[1].parent.as_deref(), Some("1"));
+    assert_eq!(result.traceability_map[0].clause_id, "clause_1.1");
+}
+
+#[test]
+fn test_markdown_without_clauses_fails() {
+    let compiler = PolicyCompiler::new();
+    assert_eq!(compiler.compile_markdown("  \n").errors, vec![CompilationError::EmptyInput]);
+    assert_eq!(compiler.compile_markdown("# Access\n```\nSYSTEM must log access.\n```\n").errors, vec![CompilationError::NoClauses]);
+}
+
+// =============================================================================
+// Policy DSL Tests
+// =============================================================================
+
+#[test]
+fn test_dsl_declarations_mix_with_text() {
+    let compiler = PolicyCompiler::builder().principal("billing-service", Principal::Service).build();
+    let policy = "\
+SYSTEM must log access.
+
+clause no-export {
+    principal USER;
+    principal billing-service as approver; // signs off exports
+    must not export \"customer records\";
+    unless incident_declared;
+}
+USER must audit logs.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass, "errors: {:?}", result.errors);
+    assert_eq!(result.intent_normalization.clauses.len(), 3);
+    assert_eq!(result.intent_normalization.polarities, vec![Polarity::Obligation, Polarity::Prohibition, Polarity::Obligation]);
+
+    let pattern = EventPattern { action: "export".to_string(), resource: Some("customer_records".to_string()) };
+    let invariant = &result.dio_invariants[1];
+    assert_eq!(invariant.predicate, InvariantPredicate::Count { pattern, min: None, max: Some(0) });
+    let unless = Guard::Not { guard: Box::new(Guard::Condition { name: "incident_declared".to_string() }) };
+    assert_eq!(invariant.guard, Some(unless.clone()));
+
+    let declared: Vec<(&str, &str, AuthorityRole)> = result.zt_authority_graph.iter()
+        .filter(|a| a.clause_index == 1)
+        .map(|a| (a.id.as_str(), a.identity.as_str(), a.role))
+        .collect();
+    assert_eq!(declared, vec![
+        ("zt_auth_1_0", "USER", AuthorityRole::Actor),
+        ("zt_auth_1_1", "billing-service", AuthorityRole::Approver),
+    ]);
+    assert!(result.zt_authority_graph.iter().filter(|a| a.clause_index == 1).all(|a| {
+        a.scope.to_string() == "export:customer_records" && a.polarity == Polarity::Prohibition && a.guard == Some(unless.clone())
+    }));
+
+    let ids: Vec<&str> = result.traceability_map.iter().map(|e| e.clause_id.as_str()).collect();
+    assert_eq!(ids, vec!["clause_0", "clause_no-export", "clause_2"]);
+    assert_eq!((result.traceability_map[1].span.line, result.traceability_map[1].span.end_line), (3, 8));
+}
+
+#[test]
+fn test_dsl_budget_compiles_to_cost_constraint() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("budget api-spend {\n  subject api;\n  ceiling 2.5k tokens per day;\n  principal SERVICE;\n  when peak_hours;\n}");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass, "errors: {:?}", result.errors);
+    assert!(result.dio_invariants.is_empty());
+    let constraint = &result.icae_constraints[0];
+    assert_eq!(constraint.subject, "api");
+    assert_eq!(constraint.measurement_unit, MeasurementUnit::Tokens);
+    assert_eq!(constraint.ceiling, Some(2500.0));
+    assert_eq!(constraint.period, Some(BudgetPeriod::Daily));
+    assert_eq!(constraint.polarity, Polarity::Prohibition);
+    assert_eq!(constraint.guard, Some(Guard::Condition { name: "peak_hours".to_string() }));
+
+    let authority = &result.zt_authority_graph[0];
+    assert_eq!(authority.scope, AuthorityScope { action: "exceed".to_string(), resource: "api".to_string() });
+    assert_eq!(authority.polarity, Polarity::Prohibition);
+    assert_eq!(result.traceability_map[0].clause_id, "clause_api-spend");
+}
+
+#[test]
+fn test_dsl_declaration_conflicts_with_text() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("USER must export customer records.\nclause no-export { principal USER; must not export \"customer records\"; }");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(result.errors[0], CompilationError::ConflictingClauses { a: 0, b: 1, .. }));
+}
+
+#[test]
+fn test_dsl_errors_point_into_declaration() {
+    let compiler = PolicyCompiler::new();
+    let policy = "SYSTEM must log access.\nbudget api {\n  principal SERVICE;\n  ceiling 1000 USD;\n}";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::InvalidDeclaration { clause_index, message, span, .. } => {
+            assert_eq!(*clause_index, 1);
+            assert_eq!(message, "'ceiling' expects AMOUNT UNIT/PERIOD");
+            assert_eq!(span.text(policy), Some("ceiling 1000 USD;"));
+            assert_eq!((span.line, span.column), (4, 3));
+        }
+        other => panic!("expected InvalidDeclaration, got {:?}", other),
+    }
+
+    let policy = "clause access-log { principal auditor; must log \"access\"; }";
+    let result = compiler.compile(policy);
+    assert_eq!(result.error_messages(), vec!["Clause 0 at 1:31 is not a valid declaration: unknown principal 'auditor'"]);
+}
+
+#[test]
+fn test_dsl_duplicate_names_are_rejected() {
+    let compiler = PolicyCompiler::new();
+    let policy = "clause export { principal USER; must export reports; }\nbudget export { principal SERVICE; ceiling 10 USD/day; }";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    match &result.errors[0] {
+        CompilationError::InvalidDeclaration { clause_index, message, span, .. } => {
+            assert_eq!(*clause_index, 1);
+            assert_eq!(message, "'export' is already declared");
+            assert_eq!(span.text(policy), Some("export"));
+            assert_eq!((span.line, span.column), (2, 8));
+        }
+        other => panic!("expected InvalidDeclaration, got {:?}", other),
+    }
+}
+
+/// Renders the artifacts of a result without the fields that echo its source text.
+fn artifacts_without_source(result: &CompilationResult) -> String {
+    let mut invariants = result.dio_invariants.clone();
+    for invariant in &mut invariants {
+        invariant.description.clear();
+        invariant.clause_text.clear();
+    }
+    let mut constraints = result.icae_constraints.clone();
+    for constraint in &mut constraints {
+        constraint.externalities.clear();
+    }
+    format!(
+        "{:?} {:?} {:?} {:?} {:?}",
+        result.intent_normalization.polarities, invariants, result.zt_authority_graph, result.delegation_graph, constraints,
+    )
+}
+
+#[test]
+fn test_dsl_declarations_match_natural_language() {
+    let compiler = PolicyCompiler::new();
+    let pairs = [
+        (
+            "Cost of inference must not exceed 100 USD per month by SERVICE.",
+            "budget inference {\n  ceiling 100 USD/month;\n  principal SERVICE;\n}",
+        ),
+        ("USER must not export reports.", "clause no-export { principal USER; must not export reports; }"),
+    ];
+
+    for (text, declaration) in pairs {
+        let from_text = compiler.compile(text);
+        let declared = compiler.compile(declaration);
+
+        assert_eq!(from_text.verdict, CompilationStatus::Pass, "{}", text);
+        assert_eq!(declared.verdict, CompilationStatus::Pass, "{}", declaration);
+        assert_eq!(artifacts_without_source(&from_text), artifacts_without_source(&declared));
+    }
+}
+
+// =============================================================================
+// Diagnostic Mode Tests
+// =============================================================================
+
+#[test]
+fn test_fail_fast_is_default_mode() {
+    let compiler = PolicyCompiler::new();
+    assert_eq!(compiler.diagnostic_mode(), DiagnosticMode::FailFast);
+
+    let result = compiler.compile("Actions should be logged by SYSTEM. Records must be audited.");
+    assert_eq!(result.errors.len(), 1);
+}
+
+#[test]
+fn test_report_all_collects_every_error_in_clause_order() {
+    let compiler = PolicyCompiler::with_diagnostic_mode(DiagnosticMode::ReportAll);
+    let policy = "Records must be audited. \
+                  Actions should be logged by SYSTEM. \
+                  The system is secure by SYSTEM. \
+                  Cost of logging cannot exceed $1000 per month by SERVICE.";
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert_eq!(result.errors.len(), 4);
+    assert!(matches!(result.errors[0], CompilationError::MissingPrincipal { clause_index: 0, .. }));
+    assert!(matches!(result.errors[1], CompilationError::ModalLanguageDetected { clause_index: 1, .. }));
+    assert!(matches!(result.errors[2], CompilationError::MissingActionVerb { clause_index: 2, .. }));
+    assert!(matches!(result.errors[3], CompilationError::MissingMeasurementUnit { clause_index: 3, .. }));
+
+    let indices: Vec<Option<usize>> = result.errors.iter().map(|e| e.clause_index()).collect();
+    let mut sorted = indices.clone();
+    sorted.sort();
+    assert_eq!(indices, sorted);
+}
+
+#[test]
+fn test_report_all_keeps_pass_order_within_clause() {
+    let compiler = PolicyCompiler::with_diagnostic_mode(DiagnosticMode::ReportAll);
+    let result = compiler.compile("Log all actions and audit them.");
+
+    assert_eq!(result.errors.len(), 2);
+    assert!(matches!(result.errors[0], CompilationError::AmbiguousMultiAction { .. }));
+    assert!(matches!(result.errors[1], CompilationError::MissingPrincipal { .. }));
+}
+
+#[test]
+fn test_report_all_passes_valid_policy() {
+    let compiler = PolicyCompiler::with_diagnostic_mode(DiagnosticMode::ReportAll);
+    let result = compiler.compile("All actions must be logged by SYSTEM. Cost of logging cannot exceed 1000 USD per month by SERVICE.");
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert!(result.errors.is_empty());
+    assert_eq!(result.icae_constraints.len(), 1);
+}
+
+// =============================================================================
+// Vocabulary Configuration Tests
+// =============================================================================
+
+#[test]
+fn test_builder_defaults_match_new() {
+    let built = PolicyCompiler::builder().build();
+    let default = PolicyCompiler::new();
+
+    assert_eq!(built.vocabulary(), default.vocabulary());
+    assert_eq!(built.diagnostic_mode(), default.diagnostic_mode());
+    assert!(default.vocabulary().action_verbs.contains(&"log".to_string()));
+}
+
+#[test]
+fn test_extended_action_verbs_accepted() {
+    let policy = "Backups are encrypted by SYSTEM.";
+    assert_eq!(PolicyCompiler::new().compile(policy).verdict, CompilationStatus::Fail);
+
+    let compiler = PolicyCompiler::builder()
+        .extend_action_verbs(["Retain", "encrypt", "prohibit"])
+        .build();
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert!(compiler.vocabulary().action_verbs.contains(&"encrypt".to_string()));
+}
+
+#[test]
+fn test_extended_modal_words_rejected() {
+    let compiler = PolicyCompiler::builder()
+        .extend_modal_words(["ideally"])
+        .build();
+    let result = compiler.compile("Ideally all actions must be logged by SYSTEM.");
+
+    assert_eq!(result.verdict, CompilationStatus::Fail);
+    assert!(matches!(
+        &result.errors[0],
+        CompilationError::ModalLanguageDetected { modal_word, .. } if modal_word == "ideally"
+    ));
+}
+
+#[test]
+fn test_replaced_vocabulary_drops_defaults() {
+    let compiler = PolicyCompiler::builder()
+        .action_verbs(["encrypt"])
+        .modal_words(Vec::<String>::new())
+        .build();
+
+    assert_eq!(compiler.compile("All actions must be logged by SYSTEM.").verdict, CompilationStatus::Fail);
+    assert_eq!(compiler.compile("Backups encrypt data by SYSTEM.").verdict, CompilationStatus::Pass);
+    assert_eq!(compiler.compile("Backups encrypt data should by SYSTEM.").verdict, CompilationStatus::Pass);
+}
+
+#[test]
+fn test_extended_cost_indicators_compile_constraints() {
+    let policy = "Egress must be tracked in bytes by SERVICE.";
+    assert!(PolicyCompiler::new().compile(policy).icae_constraints.is_empty());
+
+    let compiler = PolicyCompiler::builder()
+        .extend_cost_indicators(["egress"])
+        .diagnostic_mode(DiagnosticMode::ReportAll)
+        .build();
+    let result = compiler.compile(policy);
+
+    assert_eq!(result.verdict, CompilationStatus::Pass);
+    assert_eq!(result.icae_constraints.len(), 1);
+    assert_eq!(result.icae_constraints[0].measurement_unit, MeasurementUnit::Bytes);
+    assert_eq!(compiler.diagnostic_mode(), DiagnosticMode::ReportAll);
+}
+
 // =============================================================================
 // Thread Safety Tests
 // =============================================================================
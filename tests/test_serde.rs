@@ -0,0 +1,108 @@
+#![cfg(feature = "serde")]
+
+use poc::{
+    PolicyCompiler, CompilationResult, CompilationStatus, CompilationError, DiagnosticMode,
+    Principal, MeasurementUnit, AuthorityRole, TimeUnit, TimeSpan, RevocationTrigger, EventPattern, InvariantPredicate,
+    PolicyEvent, CostEvent, ExecutionEvent, Guard, Polarity, ExclusionTarget,
+};
+use serde_json::{json, Value};
+
+// =============================================================================
+// JSON Schema Tests
+// =============================================================================
+
+#[test]
+fn test_result_serializes_with_documented_schema() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM. Cost of logging cannot exceed 1000 USD per month by SERVICE.";
+    let result = compiler.compile(policy);
+    let value = serde_json::to_value(&result).expect("result serializes");
+
+    assert_eq!(value["verdict"], "PASS");
+    assert_eq!(value["errors"], json!([]));
+    assert!(value.get("failures").is_none(), "legacy field is not part of the schema");
+
+    assert_eq!(value["intent_normalization"]["clauses"][0]["text"], "All actions must be logged by SYSTEM");
+    assert_eq!(value["intent_normalization"]["clauses"][0]["span"]["line"], 1);
+
+    assert_eq!(value["dio_invariants"][0]["id"], "dio_0");
+    assert_eq!(value["zt_authority_graph"][0]["principal"], "SYSTEM");
+    assert_eq!(value["zt_authority_graph"][1]["principal"], "SERVICE");
+    assert_eq!(value["icae_constraints"][0]["measurement_unit"], "USD");
+    assert_eq!(value["traceability_map"][1]["clause_id"], "clause_1");
+}
+
+#[test]
+fn test_errors_are_tagged_by_kind() {
+    let compiler = PolicyCompiler::new();
+    let result = compiler.compile("Actions should be logged by SYSTEM.");
+    let value = serde_json::to_value(&result).expect("result serializes");
+
+    assert_eq!(value["verdict"], "FAIL");
+    let error = &value["errors"][0];
+    assert_eq!(error["kind"], "modal_language_detected");
+    assert_eq!(error["clause_index"], 0);
+    assert_eq!(error["modal_word"], "should");
+    assert_eq!(error["span"]["column"], 9);
+
+    let empty = serde_json::to_value(CompilationError::EmptyInput).unwrap();
+    assert_eq!(empty, json!({ "kind": "empty_input" }));
+}
+
+#[test]
+fn test_enum_string_forms() {
+    assert_eq!(serde_json::to_value(Principal::User).unwrap(), "USER");
+    assert_eq!(serde_json::to_value(MeasurementUnit::Eur).unwrap(), "EUR");
+    assert_eq!(serde_json::to_value(MeasurementUnit::Tokens).unwrap(), "tokens");
+    assert_eq!(serde_json::to_value(CompilationStatus::Fail).unwrap(), "FAIL");
+    assert_eq!(serde_json::to_value(DiagnosticMode::ReportAll).unwrap(), "report_all");
+    assert_eq!(serde_json::to_value(AuthorityRole::Approver).unwrap(), "approver");
+    assert_eq!(serde_json::to_value(TimeUnit::Days).unwrap(), "days");
+    assert_eq!(serde_json::to_value(Polarity::Prohibition).unwrap(), "prohibition");
+    assert_eq!(
+        serde_json::to_value(ExclusionTarget::Resource { name: "health_checks".to_string() }).unwrap(),
+        json!({"kind": "resource", "name": "health_checks"})
+    );
+    assert_eq!(
+        serde_json::to_value(InvariantPredicate::Count {
+            pattern: EventPattern { action: "log".to_string(), resource: None },
+            min: Some(1),
+            max: None,
+        }).unwrap(),
+        json!({"kind": "count", "pattern": {"action": "log", "resource": null}, "min": 1, "max": null})
+    );
+    assert_eq!(
+        serde_json::to_value(RevocationTrigger::After { duration: TimeSpan { amount: 30, unit: TimeUnit::Days } }).unwrap(),
+        json!({"kind": "after", "duration": {"amount": 30, "unit": "days"}})
+    );
+    assert_eq!(
+        serde_json::to_value(Guard::Not { guard: Box::new(Guard::Condition { name: "request_internal".to_string() }) }).unwrap(),
+        json!({"kind": "not", "guard": {"kind": "condition", "name": "request_internal"}})
+    );
+}
+
+#[test]
+fn test_policy_events_from_json_lines() {
+    let cost: PolicyEvent = serde_json::from_str(r#"{"type": "cost", "subject": "api", "amount": 12.5, "unit": "USD"}"#).unwrap();
+    assert_eq!(cost, PolicyEvent::Cost(CostEvent { subject: "api".to_string(), amount: 12.5, unit: MeasurementUnit::Usd, timestamp: None }));
+
+    let execution: PolicyEvent = serde_json::from_str(r#"{"type": "execution", "action": "log", "resource": "access"}"#).unwrap();
+    assert_eq!(execution, PolicyEvent::Execution(ExecutionEvent::new("log", "access")));
+}
+
+#[test]
+fn test_result_round_trips() {
+    let compiler = PolicyCompiler::new();
+    let policy = "All actions must be logged by SYSTEM. Token usage of 5000 tokens must be tracked by SERVICE.";
+    let result = compiler.compile(policy);
+
+    let json = serde_json::to_string(&result).expect("result serializes");
+    let decoded: CompilationResult = serde_json::from_str(&json).expect("result deserializes");
+
+    assert_eq!(decoded.verdict, result.verdict);
+    assert_eq!(decoded.intent_normalization.clauses, result.intent_normalization.clauses);
+    assert_eq!(decoded.icae_constraints[0].measurement_unit, MeasurementUnit::Tokens);
+    assert_eq!(decoded.traceability_map[1].cost_ids, result.traceability_map[1].cost_ids);
+    let value: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), value);
+}